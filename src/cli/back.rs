@@ -0,0 +1,18 @@
+use crate::error::Result;
+use crate::tmux::TmuxClient;
+
+use super::utils::step_jumplist;
+
+/// Goes back to the previous window in the jumplist.
+///
+/// Walks the chronological list of visited windows without reordering it,
+/// skipping windows that no longer exist.
+#[derive(clap::Parser, Debug)]
+pub struct BackCommand;
+
+impl BackCommand {
+    /// Executes the back command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        step_jumplist(client, true)
+    }
+}
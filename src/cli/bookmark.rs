@@ -0,0 +1,222 @@
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_bookmarks;
+use crate::cli::utils::{PREVIEW_CMD, switch_to_window};
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::{Bookmark, Bookmarks, WindowHistory};
+use crate::paths;
+use crate::process;
+use crate::tmux::{TmuxClient, Window};
+
+/// Saves windows under a name and brings them back.
+///
+/// Without a subcommand, opens a picker of bookmarks. Choosing one switches to its
+/// window, or recreates the window in the saved session and directory and runs the
+/// saved command if the window is gone.
+#[derive(clap::Parser, Debug)]
+pub struct BookmarkCommand {
+    #[clap(subcommand)]
+    action: Option<BookmarkAction>,
+
+    /// Show window preview in fzf
+    #[clap(short = 'v', long, default_value_t = false)]
+    preview: bool,
+
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Bookmark: ")]
+    prompt: String,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum BookmarkAction {
+    /// Save the current pane's session, window, directory and command
+    Add {
+        /// Bookmark name
+        name: String,
+
+        /// Description shown in the picker
+        #[clap(short, long, default_value = "")]
+        description: String,
+
+        /// No success message
+        #[clap(short = 'q', long, default_value_t = false)]
+        quiet: bool,
+    },
+
+    /// Delete a bookmark
+    Remove {
+        /// Bookmark name
+        #[clap(add = ArgValueCandidates::new(complete_bookmarks))]
+        name: String,
+
+        /// No success message
+        #[clap(short = 'q', long, default_value_t = false)]
+        quiet: bool,
+    },
+}
+
+impl BookmarkCommand {
+    /// Executes the bookmark command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let mut bookmarks = Bookmarks::new(paths::bookmarks_file_path());
+        bookmarks.load()?;
+
+        match &self.action {
+            Some(BookmarkAction::Add {
+                name,
+                description,
+                quiet,
+            }) => {
+                if !client.is_inside_tmux() {
+                    return Err(TsmError::NotInTmux);
+                }
+
+                let pane = client.current_pane()?;
+                let window_name = client
+                    .list_windows()
+                    .into_iter()
+                    .find(|w| w.id == pane.window_id)
+                    .map(|w| w.name)
+                    .unwrap_or_default();
+
+                bookmarks.add(Bookmark {
+                    name: name.clone(),
+                    description: description.clone(),
+                    session: pane.session_name,
+                    window_id: pane.window_id,
+                    window_name,
+                    path: pane.path,
+                    command: process::foreground_command(pane.pid).unwrap_or_default(),
+                    server: client.server_id(),
+                });
+                bookmarks.save()?;
+
+                if !quiet {
+                    client.display_message(&format!("Bookmarked as '{}'", name))?;
+                }
+                Ok(())
+            }
+            Some(BookmarkAction::Remove { name, quiet }) => {
+                if !bookmarks.remove(name) {
+                    return Err(TsmError::InvalidArgument(format!(
+                        "No bookmark named '{}'",
+                        name
+                    )));
+                }
+                bookmarks.save()?;
+
+                if !quiet {
+                    client.display_message(&format!("Removed bookmark '{}'", name))?;
+                }
+                Ok(())
+            }
+            None => self.pick(client, &mut bookmarks),
+        }
+    }
+}
+
+impl BookmarkCommand {
+    fn pick(&self, client: &TmuxClient, bookmarks: &mut Bookmarks) -> Result<()> {
+        if bookmarks.entries().is_empty() {
+            client.display_message("No bookmarks saved")?;
+            return Ok(());
+        }
+
+        let windows = client.list_windows();
+        let server = client.server_id();
+
+        let items: Vec<String> = bookmarks
+            .entries()
+            .iter()
+            .map(|b| {
+                let window = live_window(&windows, &server, b);
+                let pane_id = window.map(|w| w.pane_id.as_str()).unwrap_or("-");
+                let (session, window_name) = window
+                    .map(|w| (w.session_name.as_str(), w.name.as_str()))
+                    .unwrap_or((&b.session, &b.window_name));
+                let closed = if window.is_some() { "" } else { " (closed)" };
+                let description = if b.description.is_empty() {
+                    String::new()
+                } else {
+                    format!(" — {}", b.description)
+                };
+
+                format!(
+                    "{}\t{}\t{}{}  \x1b[2m{}:{}{}\x1b[0m",
+                    pane_id, b.name, b.name, description, session, window_name, closed
+                )
+            })
+            .collect();
+
+        let preview_cmd = if self.preview { PREVIEW_CMD } else { "" };
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(preview_cmd)
+            .with_delimiter("\t")
+            .with_nth("3..");
+
+        let Some(selection) = picker.pick(&items)? else {
+            return Ok(());
+        };
+
+        let name = selection.split('\t').nth(1).unwrap_or_default().to_string();
+        let Some(bookmark) = bookmarks.get_mut(&name) else {
+            return Ok(());
+        };
+
+        let window_id = match live_window(&windows, &server, bookmark) {
+            Some(window) => window.id.clone(),
+            None => {
+                let window_id = recreate_window(client, bookmark)?;
+                bookmark.window_id = window_id.clone();
+                bookmark.server = server;
+                bookmarks.save()?;
+                window_id
+            }
+        };
+
+        let window = client
+            .list_windows()
+            .into_iter()
+            .find(|w| w.id == window_id)
+            .ok_or_else(|| TsmError::TmuxCommand(format!("Window {} not found", window_id)))?;
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        switch_to_window(client, &window, &mut history)
+    }
+}
+
+/// The window a bookmark points at, if it is still open on the running `server`.
+///
+/// The window ID follows the window through renames and moves between sessions, but
+/// tmux hands out the same IDs again after a restart, so a bookmark saved on another
+/// server is always closed.
+fn live_window<'a>(windows: &'a [Window], server: &str, bookmark: &Bookmark) -> Option<&'a Window> {
+    if bookmark.server != server {
+        return None;
+    }
+
+    windows.iter().find(|w| w.id == bookmark.window_id)
+}
+
+/// Recreates a bookmarked window, creating its session if needed, and starts its command.
+///
+/// Returns the new window ID.
+fn recreate_window(client: &TmuxClient, bookmark: &Bookmark) -> Result<String> {
+    let window_id = if client.has_session(&bookmark.session) {
+        client.new_window(&bookmark.session, &bookmark.window_name, &bookmark.path)?
+    } else {
+        let window_id = client.new_detached_session(&bookmark.session, &bookmark.path)?;
+        client.rename_window(&window_id, &bookmark.window_name)?;
+        window_id
+    };
+
+    if !bookmark.command.is_empty() {
+        client.send_command(&window_id, &bookmark.command)?;
+    }
+
+    Ok(window_id)
+}
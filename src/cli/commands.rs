@@ -1,11 +1,17 @@
-use clap::{Parser, Subcommand, command};
+use clap::{Parser, Subcommand};
 
 use crate::{
     cli::{
-        kill::KillCommand, last_session::LastSessionCommand, last_window::LastWindowCommand,
-        move_window::MoveWindowCommand, new::NewCommand, record::RecordCommand,
-        rename::RenameCommand, swap::SwapWindowCommand, switch::SwitchCommand,
-        switch_windows::SwitchWindowCommand,
+        back::BackCommand, bookmark::BookmarkCommand, completions::CompletionsCommand,
+        cycle::CycleCommand, find_process::FindProcessCommand, forward::ForwardCommand,
+        grep::GrepCommand, jumps::JumpsCommand, kill::KillCommand, last_pane::LastPaneCommand,
+        last_session::LastSessionCommand, last_window::LastWindowCommand, mark::MarkCommand,
+        merge::MergeCommand, move_window::MoveWindowCommand, new::NewCommand, port::PortCommand,
+        preview::PreviewCommand, record::RecordCommand, rename::RenameCommand,
+        renumber::RenumberCommand, reorder::ReorderCommand, sort_windows::SortWindowsCommand,
+        split::SplitCommand, swap::SwapWindowCommand, switch::SwitchCommand,
+        switch_pane::SwitchPaneCommand, switch_windows::SwitchWindowCommand,
+        worktree::WorktreeCommand,
     },
     error::Result,
     tmux::TmuxClient,
@@ -46,6 +52,20 @@ pub enum Commands {
     #[command(alias = "sw")]
     SwitchWindow(SwitchWindowCommand),
 
+    /// Switch to a pane in any session
+    #[command(alias = "sp")]
+    SwitchPane(SwitchPaneCommand),
+
+    /// Search the scrollback of every pane and jump to a match
+    Grep(GrepCommand),
+
+    /// Find the pane running a process
+    #[command(alias = "fp")]
+    FindProcess(FindProcessCommand),
+
+    /// Find the pane listening on a TCP port
+    Port(PortCommand),
+
     /// Switch to the last active session
     #[command(alias = "ls")]
     LastSession(LastSessionCommand),
@@ -54,16 +74,66 @@ pub enum Commands {
     #[command(alias = "lw")]
     LastWindow(LastWindowCommand),
 
-    /// Record window history
-    #[command(alias = "record")]
+    /// Switch to the last focused pane, across windows and sessions
+    #[command(alias = "lp")]
+    LastPane(LastPaneCommand),
+
+    /// Record window and pane history
     Record(RecordCommand),
 
+    /// Cycle through windows or sessions in most recently used order
+    Cycle(CycleCommand),
+
+    /// Go back to the previous window in the jumplist
+    #[command(alias = "b")]
+    Back(BackCommand),
+
+    /// Go forward to the next window in the jumplist
+    #[command(alias = "f")]
+    Forward(ForwardCommand),
+
+    /// Pick a window from the jumplist
+    Jumps(JumpsCommand),
+
+    /// Pin windows to numbered slots and jump to them
+    Mark(MarkCommand),
+
+    /// Save windows under a name and bring them back
+    #[command(alias = "bm")]
+    Bookmark(BookmarkCommand),
+
     /// Move window to another session
     #[command(alias = "mv")]
     MoveWindow(MoveWindowCommand),
 
     /// Swap two windows in the same session
     SwapWindow(SwapWindowCommand),
+
+    /// Reorder the windows of a session in an editor
+    Reorder(ReorderCommand),
+
+    /// Renumber the windows of a session to close index gaps
+    Renumber(RenumberCommand),
+
+    /// Sort the windows of a session
+    SortWindows(SortWindowsCommand),
+
+    /// Move every window of a session into another and kill it
+    Merge(MergeCommand),
+
+    /// Move selected windows of a session into a new session
+    Split(SplitCommand),
+
+    /// Open a git worktree as its own session
+    #[command(alias = "wt")]
+    Worktree(WorktreeCommand),
+
+    /// Print the shell completion script
+    Completions(CompletionsCommand),
+
+    /// Render a picker preview
+    #[command(hide = true)]
+    Preview(PreviewCommand),
 }
 
 impl Cli {
@@ -74,11 +144,30 @@ impl Cli {
             Commands::Rename(cmd) => cmd.run(&client),
             Commands::Switch(cmd) => cmd.run(&client),
             Commands::SwitchWindow(cmd) => cmd.run(&client),
+            Commands::SwitchPane(cmd) => cmd.run(&client),
+            Commands::Grep(cmd) => cmd.run(&client),
+            Commands::FindProcess(cmd) => cmd.run(&client),
+            Commands::Port(cmd) => cmd.run(&client),
             Commands::LastSession(cmd) => cmd.run(&client),
             Commands::LastWindow(cmd) => cmd.run(&client),
+            Commands::LastPane(cmd) => cmd.run(&client),
             Commands::Record(cmd) => cmd.run(&client),
+            Commands::Cycle(cmd) => cmd.run(&client),
+            Commands::Back(cmd) => cmd.run(&client),
+            Commands::Forward(cmd) => cmd.run(&client),
+            Commands::Jumps(cmd) => cmd.run(&client),
+            Commands::Mark(cmd) => cmd.run(&client),
+            Commands::Bookmark(cmd) => cmd.run(&client),
             Commands::MoveWindow(cmd) => cmd.run(&client),
             Commands::SwapWindow(cmd) => cmd.run(&client),
+            Commands::Reorder(cmd) => cmd.run(&client),
+            Commands::Renumber(cmd) => cmd.run(&client),
+            Commands::SortWindows(cmd) => cmd.run(&client),
+            Commands::Merge(cmd) => cmd.run(&client),
+            Commands::Split(cmd) => cmd.run(&client),
+            Commands::Worktree(cmd) => cmd.run(&client),
+            Commands::Completions(cmd) => cmd.run(),
+            Commands::Preview(cmd) => cmd.run(&client),
         }
     }
 }
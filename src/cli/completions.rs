@@ -0,0 +1,121 @@
+use std::io;
+
+use clap::CommandFactory;
+use clap_complete::CompletionCandidate;
+use clap_complete::env::Shells;
+
+use crate::cli::Cli;
+use crate::error::{Result, TsmError};
+use crate::history::{Bookmarks, Marks};
+use crate::paths;
+use crate::tmux::TmuxClient;
+
+/// Environment variable used by the shell scripts to request dynamic completions.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Prints the shell completion script.
+///
+/// The script calls back into tsm on every <TAB>, so session and window names
+/// are completed from the running tmux server.
+#[derive(clap::Parser, Debug)]
+pub struct CompletionsCommand {
+    /// Shell to generate completions for
+    #[clap(value_parser = ["bash", "zsh", "fish", "elvish"])]
+    shell: String,
+}
+
+impl CompletionsCommand {
+    /// Executes the completions command.
+    ///
+    /// Writes the registration script for the selected shell to stdout.
+    pub fn run(&self) -> Result<()> {
+        let shells = Shells::builtins();
+        let completer = shells.completer(&self.shell).ok_or_else(|| {
+            TsmError::InvalidArgument(format!("Unsupported shell '{}'", self.shell))
+        })?;
+
+        let cmd = Cli::command();
+        let name = cmd.get_name();
+        let exe = std::env::current_exe()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| name.to_string());
+
+        completer.write_registration(COMPLETE_VAR, name, name, &exe, &mut io::stdout())?;
+        Ok(())
+    }
+}
+
+/// Completes session names.
+pub fn complete_sessions() -> Vec<CompletionCandidate> {
+    TmuxClient::new()
+        .list_session_paths()
+        .into_iter()
+        .map(|(name, _)| CompletionCandidate::new(name))
+        .collect()
+}
+
+/// Completes `session:index` window specs, with window names as help text.
+pub fn complete_window_specs() -> Vec<CompletionCandidate> {
+    TmuxClient::new()
+        .list_windows()
+        .into_iter()
+        .map(|w| {
+            CompletionCandidate::new(format!("{}:{}", w.session_name, w.index))
+                .help(Some(w.name.into()))
+        })
+        .collect()
+}
+
+/// Completes window indices in the current session, with window names as help text.
+pub fn complete_current_window_indices() -> Vec<CompletionCandidate> {
+    let client = TmuxClient::new();
+    let Ok(session) = client.current_session() else {
+        return vec![];
+    };
+
+    client
+        .list_windows()
+        .into_iter()
+        .filter(|w| w.session_name == session)
+        .map(|w| CompletionCandidate::new(w.index.to_string()).help(Some(w.name.into())))
+        .collect()
+}
+
+/// Completes mark slots, with the marked window as help text.
+pub fn complete_mark_slots() -> Vec<CompletionCandidate> {
+    let client = TmuxClient::new();
+    let mut marks = Marks::new(paths::marks_file_path(), client.server_id());
+    if marks.load().is_err() {
+        return vec![];
+    }
+
+    let windows = client.list_windows();
+
+    marks
+        .iter()
+        .map(|(slot, id)| {
+            let help = windows
+                .iter()
+                .find(|w| w.id == id)
+                .map(|w| format!("{}:{} {}", w.session_name, w.index, w.name));
+            CompletionCandidate::new(slot.to_string()).help(help.map(Into::into))
+        })
+        .collect()
+}
+
+/// Completes bookmark names, with their descriptions as help text.
+pub fn complete_bookmarks() -> Vec<CompletionCandidate> {
+    let mut bookmarks = Bookmarks::new(paths::bookmarks_file_path());
+    if bookmarks.load().is_err() {
+        return vec![];
+    }
+
+    bookmarks
+        .entries()
+        .iter()
+        .map(|b| {
+            let help = (!b.description.is_empty()).then(|| b.description.clone().into());
+            CompletionCandidate::new(&b.name).help(help)
+        })
+        .collect()
+}
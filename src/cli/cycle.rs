@@ -0,0 +1,196 @@
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::{Result, TsmError};
+use crate::history::cycle::now_millis;
+use crate::history::{Cycle, CycleScope, JumpList, WindowHistory};
+use crate::paths;
+use crate::tmux::{TmuxClient, Window};
+
+use super::utils::sort_windows_by_history;
+
+/// Direction to walk the MRU order in
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum Direction {
+    /// Towards less recently used windows
+    Next,
+    /// Back towards the most recently used window
+    Prev,
+}
+
+/// Cycles through windows or sessions in most recently used order, like Alt-Tab.
+///
+/// Repeated presses within the timeout keep walking the order captured at the first
+/// press, without recording the windows passed through. Once the presses stop, the
+/// window the walk ended on is recorded as accessed.
+#[derive(clap::Parser, Debug)]
+pub struct CycleCommand {
+    /// Direction to cycle in
+    #[clap(value_enum, required_unless_present = "commit")]
+    direction: Option<Direction>,
+
+    /// Cycle through sessions instead of windows
+    #[clap(short, long, default_value_t = false)]
+    sessions: bool,
+
+    /// Limit to windows in the current session
+    #[clap(short, long, default_value_t = false, conflicts_with = "sessions")]
+    current_session: bool,
+
+    /// Milliseconds without a press before the walk is committed
+    #[clap(short, long, default_value_t = 1000)]
+    timeout: u64,
+
+    /// Commit the walk if the press made at this time was the last one
+    #[clap(long, hide = true)]
+    commit: Option<u64>,
+}
+
+impl CycleCommand {
+    /// Executes the cycle command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        if let Some(press) = self.commit {
+            return commit(client, press);
+        }
+
+        if !client.is_inside_tmux() {
+            return Err(TsmError::NotInTmux);
+        }
+
+        let scope = if self.sessions {
+            CycleScope::Sessions
+        } else if self.current_session {
+            CycleScope::Session(client.current_session()?)
+        } else {
+            CycleScope::Windows
+        };
+
+        let mut cycle = Cycle::new(paths::cycle_file_path());
+        let resumed = cycle.load()? && cycle.is_active() && cycle.scope == scope;
+
+        if !resumed {
+            cycle.items = mru_order(client, &scope)?;
+            cycle.scope = scope;
+            cycle.position = 0;
+        }
+        cycle.timeout = self.timeout;
+
+        let windows = client.list_windows();
+        cycle.items.retain(|id| windows.iter().any(|w| w.id == *id));
+
+        let len = cycle.items.len();
+        if len < 2 {
+            cycle.clear();
+            client.display_message("Nothing to cycle to")?;
+            return Ok(());
+        }
+
+        cycle.step(matches!(self.direction, Some(Direction::Prev)));
+        cycle.last_press = now_millis();
+        cycle.save()?;
+
+        let Some(window) = windows.iter().find(|w| w.id == cycle.items[cycle.position]) else {
+            return Ok(());
+        };
+
+        if cycle.scope == CycleScope::Sessions {
+            client.switch_session(&window.session_name)?;
+        } else {
+            client.switch_to_window(&window.session_name, window.index)?;
+        }
+
+        spawn_commit(cycle.last_press)
+    }
+}
+
+/// Window IDs in MRU order within `scope`, starting with the current window.
+///
+/// For sessions, each session is represented by its most recently used window.
+fn mru_order(client: &TmuxClient, scope: &CycleScope) -> Result<Vec<String>> {
+    let mut history = WindowHistory::new(paths::history_file_path());
+    history.load()?;
+
+    let mut windows = client.list_windows();
+    if let CycleScope::Session(session) = scope {
+        windows.retain(|w| w.session_name == *session);
+    }
+
+    let mut order: Vec<Window> = sort_windows_by_history(windows, &history)
+        .into_iter()
+        .map(|(w, _)| w)
+        .collect();
+
+    // The hook may not have recorded the current window yet
+    let current = client.current_window_id()?;
+    if let Some(i) = order.iter().position(|w| w.id == current) {
+        let window = order.remove(i);
+        order.insert(0, window);
+    }
+
+    if *scope == CycleScope::Sessions {
+        let mut seen: Vec<String> = Vec::new();
+        order.retain(|w| {
+            let first = !seen.contains(&w.session_name);
+            seen.push(w.session_name.clone());
+            first
+        });
+    }
+
+    Ok(order.into_iter().map(|w| w.id).collect())
+}
+
+/// Starts a detached tsm that commits the walk once no further press arrived in time.
+fn spawn_commit(press: u64) -> Result<()> {
+    Command::new(std::env::current_exe()?)
+        .arg("cycle")
+        .arg("--commit")
+        .arg(press.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// Records the window the walk ended on, unless a later press took over.
+fn commit(client: &TmuxClient, press: u64) -> Result<()> {
+    let mut cycle = Cycle::new(paths::cycle_file_path());
+    if !cycle.load()? {
+        return Ok(());
+    }
+
+    thread::sleep(Duration::from_millis(cycle.timeout));
+
+    if !cycle.load()? || cycle.last_press != press {
+        return Ok(());
+    }
+    cycle.clear();
+
+    let Some(target) = cycle.items.get(cycle.position) else {
+        return Ok(());
+    };
+
+    let windows = client.list_windows();
+    let Some(mut window) = windows.iter().find(|w| w.id == *target) else {
+        return Ok(());
+    };
+
+    // Switching sessions lands on the session's active window
+    if cycle.scope == CycleScope::Sessions
+        && let Ok(active) = client.active_window_id(&window.session_name)
+        && let Some(active) = windows.iter().find(|w| w.id == active)
+    {
+        window = active;
+    }
+
+    let mut history = WindowHistory::new(paths::history_file_path());
+    history.load()?;
+    history.record_access(&window.session_name, window.index);
+    history.save()?;
+
+    let mut jumps = JumpList::new(paths::jumplist_file_path(), client.server_id());
+    jumps.load()?;
+    jumps.record(&window.id);
+    jumps.save()
+}
@@ -0,0 +1,141 @@
+use regex::RegexBuilder;
+
+use crate::cli::utils::{PREVIEW_CMD, switch_to_pane};
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::{PaneHistory, WindowHistory};
+use crate::paths;
+use crate::process::{self, ProcessTable};
+use crate::tmux::{Pane, TmuxClient};
+
+/// Finds the pane running a process and switches to it.
+///
+/// Matches the pattern against each pane's current command and the command lines of
+/// every process started from the pane. A single matching pane is switched to directly;
+/// several are listed in a picker. Without a pattern, every process is listed.
+#[derive(clap::Parser, Debug)]
+pub struct FindProcessCommand {
+    /// Regular expression matched against command names and command lines
+    pattern: Option<String>,
+
+    /// Case insensitive search
+    #[clap(short, long, default_value_t = false)]
+    ignore_case: bool,
+
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Process: ")]
+    prompt: String,
+}
+
+/// A process running in a pane
+struct Hit<'a> {
+    pane: &'a Pane,
+    pid: u32,
+    command: String,
+}
+
+impl FindProcessCommand {
+    /// Executes the find process command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let regex = self
+            .pattern
+            .as_deref()
+            .map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(self.ignore_case)
+                    .build()
+                    .map_err(|e| TsmError::InvalidArgument(format!("Invalid pattern: {}", e)))
+            })
+            .transpose()?;
+
+        let panes = client.list_panes();
+        let table = ProcessTable::load();
+        let own_pid = std::process::id();
+
+        let mut hits: Vec<Hit> = Vec::new();
+        for pane in &panes {
+            let found = hits.len();
+
+            for pid in table.descendants(pane.pid) {
+                if pid == own_pid {
+                    continue;
+                }
+                if let Some(command) = process::command_line(pid)
+                    && regex.as_ref().is_none_or(|r| r.is_match(&command))
+                {
+                    hits.push(Hit { pane, pid, command });
+                }
+            }
+
+            // Without /proc only the current command is known
+            if hits.len() == found && regex.as_ref().is_none_or(|r| r.is_match(&pane.command)) {
+                hits.push(Hit {
+                    pane,
+                    pid: pane.pid,
+                    command: pane.command.clone(),
+                });
+            }
+        }
+
+        let pane = match hits.first() {
+            None => {
+                let pattern = self.pattern.as_deref().unwrap_or_default();
+                client.display_message(&format!("No process matches {}", pattern))?;
+                return Ok(());
+            }
+            // Without a pattern the picker is the point, even for a single pane
+            Some(first)
+                if self.pattern.is_some()
+                    && hits.iter().all(|hit| hit.pane.id == first.pane.id) =>
+            {
+                first.pane
+            }
+            Some(_) => match self.pick(&hits)? {
+                Some(pane) => pane,
+                None => return Ok(()), // User canceled
+            },
+        };
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        let mut pane_history =
+            PaneHistory::new(paths::pane_history_file_path(), client.server_id());
+        pane_history.load()?;
+        switch_to_pane(client, pane, &mut history, &mut pane_history)
+    }
+
+    fn pick<'a>(&self, hits: &[Hit<'a>]) -> Result<Option<&'a Pane>> {
+        let items: Vec<String> = hits
+            .iter()
+            .map(|hit| {
+                format!(
+                    "{}\t{}\t{}:{}.{}  {}",
+                    hit.pane.id,
+                    hit.pid,
+                    hit.pane.session_name,
+                    hit.pane.window_index,
+                    hit.pane.index,
+                    hit.command
+                )
+            })
+            .collect();
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(PREVIEW_CMD)
+            .with_delimiter("\t")
+            .with_nth("3..");
+
+        let Some(selection) = picker.pick(&items)? else {
+            return Ok(None);
+        };
+
+        let pane_id = selection.split('\t').next().unwrap_or_default();
+        hits.iter()
+            .find(|hit| hit.pane.id == pane_id)
+            .map(|hit| Some(hit.pane))
+            .ok_or_else(|| {
+                TsmError::InvalidArgument(format!("Selected pane {} not found", pane_id))
+            })
+    }
+}
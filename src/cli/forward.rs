@@ -0,0 +1,18 @@
+use crate::error::Result;
+use crate::tmux::TmuxClient;
+
+use super::utils::step_jumplist;
+
+/// Goes forward to the next window in the jumplist.
+///
+/// Only has somewhere to go after `tsm back`; visiting another window drops the
+/// entries ahead of the current one.
+#[derive(clap::Parser, Debug)]
+pub struct ForwardCommand;
+
+impl ForwardCommand {
+    /// Executes the forward command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        step_jumplist(client, false)
+    }
+}
@@ -0,0 +1,232 @@
+use regex::{Regex, RegexBuilder};
+use unicode_width::UnicodeWidthChar;
+
+use crate::cli::utils::{preview_command, switch_to_pane};
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::{PaneHistory, WindowHistory};
+use crate::paths;
+use crate::tmux::{Pane, TmuxClient};
+
+/// Searches the scrollback of every pane and jumps to a match.
+///
+/// Lists each matching line labelled with its pane, previews the pane around the hit,
+/// and on choice switches to the pane and enters copy mode at that line.
+#[derive(clap::Parser, Debug)]
+pub struct GrepCommand {
+    /// Regular expression to search for
+    pattern: String,
+
+    /// Case insensitive search
+    #[clap(short, long, default_value_t = false)]
+    ignore_case: bool,
+
+    /// Scrollback lines searched in each pane
+    #[clap(short = 'n', long, default_value_t = 10000)]
+    lines: u32,
+
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Match: ")]
+    prompt: String,
+}
+
+/// A matching line, counted from the top of the pane's scrollback
+struct Hit<'a> {
+    pane: &'a Pane,
+    line: u32,
+    column: usize,
+    text: String,
+}
+
+impl GrepCommand {
+    /// Executes the grep command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let regex = RegexBuilder::new(&self.pattern)
+            .case_insensitive(self.ignore_case)
+            .build()
+            .map_err(|e| TsmError::InvalidArgument(format!("Invalid pattern: {}", e)))?;
+
+        let panes = client.list_panes();
+        let hits: Vec<Hit> = panes
+            .iter()
+            .flat_map(|pane| self.search_pane(client, pane, &regex))
+            .collect();
+
+        if hits.is_empty() {
+            client.display_message(&format!("No matches for {}", self.pattern))?;
+            return Ok(());
+        }
+
+        let items: Vec<String> = hits
+            .iter()
+            .map(|hit| {
+                format!(
+                    "{}\t{}\t{}\t{}:{}.{}  {}",
+                    hit.pane.id,
+                    hit.line,
+                    hit.column,
+                    hit.pane.session_name,
+                    hit.pane.window_index,
+                    hit.pane.index,
+                    hit.text
+                )
+            })
+            .collect();
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(&preview_command("pane {1} --line {2}"))
+            .with_delimiter("\t")
+            .with_nth("4..");
+
+        let selection = match picker.pick(&items)? {
+            Some(sel) => sel,
+            None => return Ok(()), // User canceled
+        };
+
+        let mut fields = selection.split('\t');
+        let pane_id = fields.next().unwrap_or_default();
+        let hit = fields
+            .next()
+            .and_then(|line| line.parse::<u32>().ok())
+            .and_then(|line| {
+                hits.iter()
+                    .find(|hit| hit.pane.id == pane_id && hit.line == line)
+            })
+            .ok_or_else(|| {
+                TsmError::InvalidArgument("Failed to parse fzf selection for match".to_string())
+            })?;
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        let mut pane_history =
+            PaneHistory::new(paths::pane_history_file_path(), client.server_id());
+        pane_history.load()?;
+        switch_to_pane(client, hit.pane, &mut history, &mut pane_history)?;
+
+        jump_to_line(client, hit.pane, hit.line, hit.column)
+    }
+
+    /// Matching lines of a pane, with the match highlighted. Panes that cannot be
+    /// captured are skipped.
+    fn search_pane<'a>(&self, client: &TmuxClient, pane: &'a Pane, regex: &Regex) -> Vec<Hit<'a>> {
+        let (Ok(history_size), Ok(contents)) = (
+            client.history_size(&pane.id),
+            client.capture_scrollback(&pane.id, self.lines),
+        ) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = contents.lines().collect();
+        let first_line = first_captured_line(history_size, lines.len(), pane.height);
+
+        lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, text)| {
+                let m = regex.find(text)?;
+                Some(Hit {
+                    pane,
+                    line: first_line + i as u32,
+                    column: cursor_steps(&text[..m.start()]),
+                    text: format!(
+                        "{}\x1b[1;31m{}\x1b[0m{}",
+                        &text[..m.start()],
+                        m.as_str(),
+                        &text[m.end()..]
+                    )
+                    .trim()
+                    .to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Number of `cursor-right` presses needed to move past `text` in copy mode.
+///
+/// The cursor steps over one character at a time, skipping the padding of wide
+/// characters, while zero-width characters such as combining marks share the cell of
+/// the character before them.
+fn cursor_steps(text: &str) -> usize {
+    text.chars()
+        .filter(|c| c.width().is_some_and(|width| width > 0))
+        .count()
+}
+
+/// Line number, counted from the top of the scrollback, of the first of `captured`
+/// lines that end with the visible area of a pane `height` lines tall.
+fn first_captured_line(history_size: u32, captured: usize, height: u32) -> u32 {
+    let captured_history = captured.saturating_sub(height as usize) as u32;
+    history_size.saturating_sub(captured_history)
+}
+
+/// Scroll offset for `goto-line` and `cursor-down` presses from the top line that put
+/// the cursor on `line`, centring it in a pane `height` lines tall where possible.
+fn copy_mode_moves(line: u32, history_size: u32, height: u32) -> (u32, u32) {
+    // Line relative to the top of the visible area, negative in the scrollback
+    let row = line as i64 - history_size as i64;
+    let offset = (height as i64 / 2 - row).clamp(0, history_size as i64);
+    (offset as u32, (row + offset).max(0) as u32)
+}
+
+/// Enters copy mode in `pane` with the cursor on `line` (counted from the top of the
+/// scrollback) and `column`, scrolled so the line is centred where possible.
+fn jump_to_line(client: &TmuxClient, pane: &Pane, line: u32, column: usize) -> Result<()> {
+    let history_size = client.history_size(&pane.id)?;
+    let (offset, down) = copy_mode_moves(line, history_size, pane.height);
+
+    client.copy_mode(&pane.id)?;
+    client.send_copy_command(&pane.id, &["goto-line", &offset.to_string()], 1)?;
+    client.send_copy_command(&pane.id, &["top-line"], 1)?;
+    client.send_copy_command(&pane.id, &["start-of-line"], 1)?;
+
+    if down > 0 {
+        client.send_copy_command(&pane.id, &["cursor-down"], down)?;
+    }
+    if column > 0 {
+        client.send_copy_command(&pane.id, &["cursor-right"], column as u32)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_steps_count_characters_not_cells() {
+        assert_eq!(cursor_steps(""), 0);
+        assert_eq!(cursor_steps("abc "), 4);
+        assert_eq!(cursor_steps("日本 "), 3);
+        assert_eq!(cursor_steps("cafe\u{301} "), 5);
+    }
+
+    #[test]
+    fn captured_lines_are_numbered_from_the_top_of_the_scrollback() {
+        // Part of the history was captured: 30 history lines and 20 visible ones
+        assert_eq!(first_captured_line(1000, 50, 20), 970);
+        // The whole history was captured
+        assert_eq!(first_captured_line(10, 30, 20), 0);
+        // Trailing empty lines are not captured
+        assert_eq!(first_captured_line(100, 5, 20), 100);
+    }
+
+    #[test]
+    fn visible_lines_are_reached_without_scrolling() {
+        assert_eq!(copy_mode_moves(1019, 1000, 20), (0, 19));
+        assert_eq!(copy_mode_moves(3, 0, 20), (0, 3));
+    }
+
+    #[test]
+    fn scrollback_lines_are_centred() {
+        assert_eq!(copy_mode_moves(500, 1000, 20), (510, 10));
+        assert_eq!(copy_mode_moves(995, 1000, 20), (15, 10));
+    }
+
+    #[test]
+    fn oldest_lines_scroll_to_the_top() {
+        assert_eq!(copy_mode_moves(5, 1000, 20), (1000, 5));
+    }
+}
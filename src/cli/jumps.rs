@@ -0,0 +1,83 @@
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::{JumpList, WindowHistory};
+use crate::paths;
+use crate::tmux::TmuxClient;
+
+use super::utils::{PREVIEW_CMD, switch_to_window};
+
+/// Shows the jumplist in fzf and jumps to the selected entry.
+///
+/// Entries are listed newest first, with the current position marked. Windows that
+/// no longer exist are left out.
+#[derive(clap::Parser, Debug)]
+pub struct JumpsCommand {
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Jump to: ")]
+    prompt: String,
+
+    /// Show window preview in fzf
+    #[clap(short = 'v', long, default_value_t = false)]
+    preview: bool,
+}
+
+impl JumpsCommand {
+    /// Executes the jumps command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let mut jumps = JumpList::new(paths::jumplist_file_path(), client.server_id());
+        jumps.load()?;
+
+        let windows = client.list_windows();
+
+        let items: Vec<String> = jumps
+            .entries()
+            .iter()
+            .enumerate()
+            .rev()
+            .filter_map(|(i, id)| {
+                let window = windows.iter().find(|w| w.id == *id)?;
+                let marker = if i == jumps.position() { '>' } else { ' ' };
+                Some(format!(
+                    "{}\t{}\t{} {}:{} {}",
+                    window.pane_id, i, marker, window.session_name, window.index, window.name
+                ))
+            })
+            .collect();
+
+        if items.is_empty() {
+            client.display_message("Jumplist is empty")?;
+            return Ok(());
+        }
+
+        let preview_cmd = if self.preview { PREVIEW_CMD } else { "" };
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(preview_cmd)
+            .with_delimiter("\t")
+            .with_nth("3..");
+
+        let Some(selection) = picker.pick(&items)? else {
+            return Ok(());
+        };
+
+        let position: usize = selection
+            .split('\t')
+            .nth(1)
+            .and_then(|i| i.parse().ok())
+            .ok_or_else(|| TsmError::InvalidArgument("Invalid jumplist selection".to_string()))?;
+
+        let window = jumps
+            .entries()
+            .get(position)
+            .and_then(|id| windows.iter().find(|w| w.id == *id))
+            .ok_or_else(|| TsmError::InvalidArgument("Window no longer exists".to_string()))?;
+
+        jumps.set_position(position);
+        jumps.save()?;
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        switch_to_window(client, window, &mut history)
+    }
+}
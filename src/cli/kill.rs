@@ -1,20 +1,41 @@
+use std::io::IsTerminal;
+use std::path::Path;
+
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_sessions;
+use crate::cli::utils::preview_command;
 use crate::error::Result;
 use crate::fzf::FzfPicker;
+use crate::git::Repository;
 use crate::tmux::TmuxClient;
 
 /// Kills one or more tmux sessions.
 ///
 /// Can kill a specific session by name, prompt for selection via fzf, or kill all sessions.
+/// Sessions rooted in a linked git worktree offer to remove the worktree as well.
 #[derive(clap::Parser, Debug)]
 pub struct KillCommand {
     /// Session name
-    #[clap(short, long)]
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
     session: Option<String>,
 
     /// Kill all
     #[clap(short = 'a', long, default_value_t = false)]
     all: bool,
 
+    /// Remove the session's git worktree without asking
+    #[clap(long, default_value_t = false, conflicts_with = "keep_worktree")]
+    remove_worktree: bool,
+
+    /// Keep the session's git worktree without asking
+    #[clap(long, default_value_t = false)]
+    keep_worktree: bool,
+
+    /// Show session preview in fzf
+    #[clap(short = 'v', long, default_value_t = false)]
+    preview: bool,
+
     /// fzf prompt
     #[clap(short = 'P', long, default_value = "Kill session: ")]
     prompt: String,
@@ -38,7 +59,15 @@ impl KillCommand {
         let target = match self.session.clone() {
             Some(n) => n,
             None => {
-                let picker = FzfPicker::new().with_prompt(&self.prompt);
+                let preview_cmd = if self.preview {
+                    preview_command("session -- {}")
+                } else {
+                    String::new()
+                };
+
+                let picker = FzfPicker::new()
+                    .with_prompt(&self.prompt)
+                    .with_preview_command(&preview_cmd);
                 let sessions = client.list_sessions();
                 match picker.pick(&sessions)? {
                     Some(selection) => selection,
@@ -47,12 +76,55 @@ impl KillCommand {
             }
         };
 
+        // Ask before killing: the popup running tsm may belong to this session
+        let worktree = client
+            .list_session_paths()
+            .into_iter()
+            .find(|(name, _)| *name == target)
+            .and_then(|(_, path)| Repository::open(Path::new(&path)))
+            .filter(|repo| repo.is_linked_worktree());
+
+        let remove_worktree = match &worktree {
+            Some(repo) => self.should_remove_worktree(repo)?,
+            None => false,
+        };
+
+        // Remove the worktree first: a dirty or locked worktree aborts before the session
+        // is gone, and killing the session may kill this process too
+        let removed = worktree.filter(|_| remove_worktree);
+        if let Some(repo) = &removed {
+            repo.remove_worktree(&repo.root)?;
+        }
+
         client.kill_session(&target)?;
 
         if !self.quiet {
-            client.display_message(&format!("Killed session: {}", target))?;
+            let message = match removed {
+                Some(repo) => format!(
+                    "Killed session {} and removed worktree {}",
+                    target,
+                    repo.root.display()
+                ),
+                None => format!("Killed session: {}", target),
+            };
+            client.display_message(&message)?;
         }
 
         Ok(())
     }
+
+    fn should_remove_worktree(&self, repo: &Repository) -> Result<bool> {
+        if self.remove_worktree || self.keep_worktree {
+            return Ok(self.remove_worktree);
+        }
+
+        if !std::io::stdin().is_terminal() {
+            return Ok(false);
+        }
+
+        let remove_item = format!("Remove worktree {}", repo.root.display());
+        let picker = FzfPicker::new().with_prompt("Session is a git worktree: ");
+
+        Ok(picker.pick(&["Keep worktree".to_string(), remove_item.clone()])? == Some(remove_item))
+    }
 }
@@ -0,0 +1,43 @@
+use crate::error::{Result, TsmError};
+use crate::history::{PaneHistory, WindowHistory};
+use crate::paths;
+use crate::tmux::TmuxClient;
+
+use super::utils::switch_to_pane;
+
+/// Switches to the previously focused pane.
+///
+/// Unlike `last-window`, this returns to the exact pane, even when it is in another
+/// window or session. Pane focus is recorded by `tsm record`.
+#[derive(clap::Parser, Debug)]
+pub struct LastPaneCommand;
+
+impl LastPaneCommand {
+    /// Executes the last pane command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        if !client.is_inside_tmux() {
+            return Err(TsmError::NotInTmux);
+        }
+
+        let current = client.current_pane()?;
+        let panes = client.list_panes();
+
+        let mut pane_history =
+            PaneHistory::new(paths::pane_history_file_path(), client.server_id());
+        pane_history.load()?;
+        pane_history.record(&current.id);
+
+        let target = pane_history
+            .previous(&current.id, |id| panes.iter().any(|p| p.id == id))
+            .and_then(|id| panes.iter().find(|p| p.id == id));
+
+        let Some(pane) = target else {
+            client.display_message("No previous pane found")?;
+            return Ok(());
+        };
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        switch_to_pane(client, pane, &mut history, &mut pane_history)
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_mark_slots;
+use crate::cli::utils::{PREVIEW_CMD, edit_in_editor, switch_to_window};
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::{Marks, WindowHistory};
+use crate::paths;
+use crate::tmux::{TmuxClient, Window};
+
+const INSTRUCTIONS: &str = "\
+# Each line above is a slot, starting at 1. Reorder lines to move marks,
+# delete a line to remove a mark, or write '-' to leave a slot empty.
+# The first column is the window ID; the rest is informational.
+# Lines starting with '#' are ignored. Leave the file unchanged to abort.
+";
+
+/// Pins windows to numbered slots, harpoon style.
+///
+/// Marks point at windows by their tmux window ID, so they follow windows that are
+/// moved between sessions, swapped or renumbered.
+#[derive(clap::Parser, Debug)]
+pub struct MarkCommand {
+    #[clap(subcommand)]
+    action: MarkAction,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum MarkAction {
+    /// Pin the current window to a slot
+    Set {
+        /// Slot number
+        #[clap(value_parser = clap::value_parser!(u32).range(1..))]
+        slot: u32,
+
+        /// No success message
+        #[clap(short = 'q', long, default_value_t = false)]
+        quiet: bool,
+    },
+
+    /// Switch to the window pinned to a slot
+    Jump {
+        /// Slot number
+        #[clap(add = ArgValueCandidates::new(complete_mark_slots))]
+        slot: u32,
+    },
+
+    /// Pick a marked window in fzf
+    List {
+        /// Show window preview in fzf
+        #[clap(short = 'v', long, default_value_t = false)]
+        preview: bool,
+
+        /// fzf prompt
+        #[clap(short = 'P', long, default_value = "Marks: ")]
+        prompt: String,
+    },
+
+    /// Reorder or remove marks in an editor
+    Edit {
+        /// No success message
+        #[clap(short = 'q', long, default_value_t = false)]
+        quiet: bool,
+    },
+}
+
+impl MarkCommand {
+    /// Executes the mark command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let mut marks = Marks::new(paths::marks_file_path(), client.server_id());
+        marks.load()?;
+
+        match &self.action {
+            MarkAction::Set { slot, quiet } => {
+                if !client.is_inside_tmux() {
+                    return Err(TsmError::NotInTmux);
+                }
+
+                marks.set(*slot, &client.current_window_id()?);
+                marks.save()?;
+
+                if !quiet {
+                    client.display_message(&format!("Marked window as {}", slot))?;
+                }
+                Ok(())
+            }
+            MarkAction::Jump { slot } => {
+                let window_id = marks.get(*slot).ok_or_else(|| {
+                    TsmError::InvalidArgument(format!("Mark {} is not set", slot))
+                })?;
+
+                let window = client
+                    .list_windows()
+                    .into_iter()
+                    .find(|w| w.id == window_id)
+                    .ok_or_else(|| {
+                        TsmError::InvalidArgument(format!(
+                            "Window of mark {} no longer exists",
+                            slot
+                        ))
+                    })?;
+
+                jump(client, &window)
+            }
+            MarkAction::List { preview, prompt } => list(client, &marks, *preview, prompt),
+            MarkAction::Edit { quiet } => {
+                let windows = client.list_windows();
+
+                match edit_marks(&marks, &windows)? {
+                    Some(slots) => {
+                        marks.replace(slots);
+                        marks.save()?;
+                        if !quiet {
+                            client.display_message("Marks updated")?;
+                        }
+                    }
+                    None if !quiet => client.display_message("Marks unchanged")?,
+                    None => {}
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn jump(client: &TmuxClient, window: &Window) -> Result<()> {
+    let mut history = WindowHistory::new(paths::history_file_path());
+    history.load()?;
+    switch_to_window(client, window, &mut history)
+}
+
+fn list(client: &TmuxClient, marks: &Marks, preview: bool, prompt: &str) -> Result<()> {
+    let windows = client.list_windows();
+
+    let items: Vec<String> = marks
+        .iter()
+        .filter_map(|(slot, id)| {
+            let window = windows.iter().find(|w| w.id == id)?;
+            Some(format!(
+                "{}\t{}\t{}: {}:{} {}",
+                window.pane_id, slot, slot, window.session_name, window.index, window.name
+            ))
+        })
+        .collect();
+
+    if items.is_empty() {
+        client.display_message("No marks set")?;
+        return Ok(());
+    }
+
+    let preview_cmd = if preview { PREVIEW_CMD } else { "" };
+
+    let picker = FzfPicker::new()
+        .with_prompt(prompt)
+        .with_preview_command(preview_cmd)
+        .with_delimiter("\t")
+        .with_nth("3..");
+
+    let Some(selection) = picker.pick(&items)? else {
+        return Ok(());
+    };
+
+    let pane_id = selection.split('\t').next().unwrap_or_default();
+    match windows.iter().find(|w| w.pane_id == pane_id) {
+        Some(window) => jump(client, window),
+        None => Ok(()),
+    }
+}
+
+/// Let the user edit the marks, returning `None` if they did not change.
+///
+/// Each non-comment line is a slot, so the order of the lines assigns slot numbers.
+fn edit_marks(marks: &Marks, windows: &[Window]) -> Result<Option<BTreeMap<u32, String>>> {
+    let last_slot = marks.iter().map(|(slot, _)| slot).max().unwrap_or(0);
+
+    let mut contents = String::new();
+    for slot in 1..=last_slot {
+        match marks.get(slot) {
+            Some(id) => match windows.iter().find(|w| w.id == id) {
+                Some(w) => contents.push_str(&format!(
+                    "{}\t{}:{}\t{}\n",
+                    id, w.session_name, w.index, w.name
+                )),
+                None => contents.push_str(&format!("{}\t(closed)\n", id)),
+            },
+            None => contents.push_str("-\n"),
+        }
+    }
+    contents.push('\n');
+    contents.push_str(INSTRUCTIONS);
+
+    let edited = edit_in_editor("marks", &contents)?;
+    if edited == contents {
+        return Ok(None);
+    }
+
+    let mut slots = BTreeMap::new();
+    let lines = edited
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    for (slot, line) in (1..).zip(lines) {
+        let id = line.split_whitespace().next().unwrap_or_default();
+        if id == "-" {
+            continue;
+        }
+
+        if !id.starts_with('@') {
+            return Err(TsmError::InvalidArgument(format!(
+                "Invalid window ID '{}' for mark {}",
+                id, slot
+            )));
+        }
+
+        if !slots.values().any(|existing| existing == id) {
+            slots.insert(slot, id.to_string());
+        }
+    }
+
+    Ok(Some(slots))
+}
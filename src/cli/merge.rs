@@ -0,0 +1,124 @@
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_sessions;
+use crate::cli::utils::{all_sessions, migrate_history, resolve_session, session_windows};
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::WindowHistory;
+use crate::paths;
+use crate::tmux::TmuxClient;
+
+/// Merges one session into another.
+///
+/// Moves every window of the source session into the target session, keeping their order
+/// and names, then kills the source session. Window history follows the moved windows.
+#[derive(clap::Parser, Debug)]
+pub struct MergeCommand {
+    /// Session to merge (defaults to the current session)
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
+    from: Option<String>,
+
+    /// Session to merge into (prompts for selection if not provided)
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
+    into: Option<String>,
+
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Merge into session: ")]
+    prompt: String,
+
+    /// No success message
+    #[clap(short = 'q', long, default_value_t = false)]
+    quiet: bool,
+}
+
+impl MergeCommand {
+    /// Executes the merge command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let from = resolve_session(client, self.from.as_deref())?;
+        if !client.has_session(&from) {
+            return Err(TsmError::InvalidArgument(format!(
+                "Session '{}' not found",
+                from
+            )));
+        }
+
+        let into = match self.into.clone() {
+            Some(into) => into,
+            None => {
+                let items: Vec<String> = all_sessions(client)
+                    .into_iter()
+                    .filter(|s| *s != from)
+                    .collect();
+                let picker = FzfPicker::new().with_prompt(&self.prompt);
+                match picker.pick(&items)? {
+                    Some(selection) => selection,
+                    None => return Ok(()),
+                }
+            }
+        };
+
+        if into == from {
+            return Err(TsmError::InvalidArgument(
+                "Cannot merge a session into itself".to_string(),
+            ));
+        }
+
+        if !client.has_session(&into) {
+            return Err(TsmError::InvalidArgument(format!(
+                "Session '{}' not found",
+                into
+            )));
+        }
+
+        let windows = session_windows(client, &from);
+        if windows.is_empty() {
+            return Err(TsmError::TmuxCommand(format!(
+                "No windows found in session '{}'",
+                from
+            )));
+        }
+
+        // Follow the pane the client is on if its session is about to disappear
+        let followed_pane = if client.is_inside_tmux()
+            && client.current_session().ok().as_deref() == Some(from.as_str())
+        {
+            let (_, index) = client.get_current_window()?;
+            let pane_id = client.get_pane_id(&from, index)?;
+            client.switch_session(&into)?;
+            Some(pane_id)
+        } else {
+            None
+        };
+
+        for window in &windows {
+            client.move_window(&from, window.index, &into)?;
+        }
+
+        if client.has_session(&from) {
+            client.kill_session(&from)?;
+        }
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        migrate_history(client, &windows, &mut history);
+
+        if let Some(pane_id) = followed_pane {
+            let (session, window_index) = client.find_window_by_pane_id(&pane_id)?;
+            client.switch_to_window(&session, window_index)?;
+            history.record_access(&session, window_index);
+        }
+
+        history.save()?;
+
+        if !self.quiet {
+            client.display_message(&format!(
+                "Merged {} windows from {} into {}",
+                windows.len(),
+                from,
+                into
+            ))?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,14 +1,33 @@
+pub mod back;
+pub mod bookmark;
 pub mod commands;
+pub mod completions;
+pub mod cycle;
+pub mod find_process;
+pub mod forward;
+pub mod grep;
+pub mod jumps;
 pub mod kill;
+pub mod last_pane;
 pub mod last_session;
 pub mod last_window;
+pub mod mark;
+pub mod merge;
 pub mod move_window;
 pub mod new;
+pub mod port;
+pub mod preview;
 pub mod record;
 pub mod rename;
+pub mod renumber;
+pub mod reorder;
+pub mod sort_windows;
+pub mod split;
 pub mod swap;
 pub mod switch;
+pub mod switch_pane;
 pub mod switch_windows;
 mod utils;
+pub mod worktree;
 
 pub use commands::Cli;
@@ -1,26 +1,42 @@
+use std::path::Path;
+
+use clap_complete::ArgValueCandidates;
+
 use crate::{
     TmuxClient,
-    cli::utils::{PREVIEW_CMD, sort_windows_by_history},
+    cli::{
+        completions::{complete_sessions, complete_window_specs},
+        utils::{PREVIEW_CMD, all_sessions, prompt_input, sort_windows_by_history},
+    },
     error::Result,
     fzf::FzfPicker,
     history::WindowHistory,
+    naming::sanitise_session_name,
     paths,
 };
 
+/// Entry in the target session picker that creates a new session
+const NEW_SESSION_ITEM: &str = "＋ new session";
+
 /// Moves a window from one session to another.
 ///
 /// Can move a specified window or prompt for selection via fzf.
 /// If the source session is not specified and a target is, defaults to the current window.
+/// The target can also be a new session, which is rooted in the window's working directory.
 #[derive(clap::Parser, Debug)]
 pub struct MoveWindowCommand {
-    /// From session name
-    #[clap(short, long)]
+    /// Window to move, as `session:index`
+    #[clap(short, long, add = ArgValueCandidates::new(complete_window_specs))]
     from: Option<String>,
 
     /// To session name
-    #[clap(short, long)]
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
     to: Option<String>,
 
+    /// Move the window into a new session with this name
+    #[clap(short, long, conflicts_with = "to")]
+    new_session: Option<String>,
+
     /// No success message
     #[clap(short = 'q', long, default_value_t = false)]
     quiet: bool,
@@ -35,9 +51,9 @@ impl MoveWindowCommand {
             return Err(crate::error::TsmError::NotInTmux);
         }
 
-        let sessions = client.list_sessions();
+        let sessions = all_sessions(client);
 
-        if sessions.len() < 2 {
+        if self.to.is_some() && sessions.len() < 2 {
             return Err(crate::error::TsmError::InvalidArgument(
                 "At least two sessions are required to move a window".to_string(),
             ));
@@ -46,20 +62,21 @@ impl MoveWindowCommand {
         let mut history = WindowHistory::new(paths::history_file_path());
         history.load()?;
 
-        let window_address = if self.from.is_none() && self.to.is_some() {
-            let current_window = client.get_current_window()?;
-            Some(current_window)
-        } else {
-            let windows = client.list_windows();
+        let window_address =
+            if self.from.is_none() && (self.to.is_some() || self.new_session.is_some()) {
+                let current_window = client.get_current_window()?;
+                Some(current_window)
+            } else {
+                let windows = client.list_windows();
 
-            let indexed_windows = sort_windows_by_history(windows, &history);
-            let window_items: Vec<String> = indexed_windows
-                .iter()
-                .map(|(w, _)| format!("{}\t {}:{}", w.pane_id, w.session_name, w.index))
-                .collect();
+                let indexed_windows = sort_windows_by_history(windows, &history);
+                let window_items: Vec<String> = indexed_windows
+                    .iter()
+                    .map(|(w, _)| format!("{}\t {}:{}", w.pane_id, w.session_name, w.index))
+                    .collect();
 
-            find_window_to_move(&window_items, &self.from)?
-        };
+                find_window_to_move(&window_items, &self.from)?
+            };
 
         if let Some((from_session, from_window_index)) = window_address {
             let sessions_items = sessions
@@ -68,16 +85,50 @@ impl MoveWindowCommand {
                 .map(|s| s.to_string())
                 .collect::<Vec<String>>();
 
-            let target_session = find_target_session(&sessions_items, &self.to)?;
-
-            if let Some(to_session) = target_session {
+            let target_session = if let Some(name) = &self.new_session {
+                Some(Target::New(sanitise_session_name(name)))
+            } else {
+                find_target_session(
+                    client,
+                    &sessions_items,
+                    &self.to,
+                    &from_session,
+                    from_window_index,
+                )?
+            };
+
+            if let Some(target) = target_session {
                 let pane_id = client.get_pane_id(&from_session, from_window_index)?;
 
-                if client.is_last_window_in_session(&from_session) {
-                    client.switch_session(&to_session)?;
-                }
-
-                client.move_window(&from_session, from_window_index, &to_session)?;
+                let to_session = match target {
+                    Target::Existing(to_session) => {
+                        if client.is_last_window_in_session(&from_session) {
+                            client.switch_session(&to_session)?;
+                        }
+
+                        client.move_window(&from_session, from_window_index, &to_session)?;
+                        to_session
+                    }
+                    Target::New(to_session) => {
+                        if client.has_session(&to_session) {
+                            return Err(crate::error::TsmError::InvalidArgument(format!(
+                                "Session '{}' already exists",
+                                to_session
+                            )));
+                        }
+
+                        let path =
+                            client.get_pane_current_path(&from_session, from_window_index)?;
+                        let placeholder = client.new_detached_session(&to_session, &path)?;
+
+                        if client.is_last_window_in_session(&from_session) {
+                            client.switch_session(&to_session)?;
+                        }
+
+                        client.replace_window(&from_session, from_window_index, &placeholder)?;
+                        to_session
+                    }
+                };
 
                 let (session, new_window_index) = client.find_window_by_pane_id(&pane_id)?;
 
@@ -131,14 +182,39 @@ fn find_window_to_move(items: &[String], from: &Option<String>) -> Result<Option
     }
 }
 
-fn find_target_session(items: &[String], to: &Option<String>) -> Result<Option<String>> {
+/// Session a window is moved into
+enum Target {
+    Existing(String),
+    New(String),
+}
+
+fn find_target_session(
+    client: &TmuxClient,
+    items: &[String],
+    to: &Option<String>,
+    from_session: &str,
+    from_window_index: u32,
+) -> Result<Option<Target>> {
     if let Some(session_spec) = to {
-        return Ok(Some(session_spec.clone()));
+        return Ok(Some(Target::Existing(session_spec.clone())));
     }
 
+    let mut items = items.to_vec();
+    items.push(NEW_SESSION_ITEM.to_string());
+
     let picker = FzfPicker::new().with_prompt("Select target session: ");
-    match picker.pick(items)? {
-        Some(selection) => Ok(Some(selection)),
+    match picker.pick(&items)? {
+        Some(selection) if selection == NEW_SESSION_ITEM => {
+            let path = client.get_pane_current_path(from_session, from_window_index)?;
+            let default_name = Path::new(&path)
+                .file_name()
+                .map(|name| sanitise_session_name(&name.to_string_lossy()))
+                .unwrap_or_else(|| "_".to_string());
+
+            Ok(prompt_input("New session name", &default_name)?
+                .map(|name| Target::New(sanitise_session_name(&name))))
+        }
+        Some(selection) => Ok(Some(Target::Existing(selection))),
         None => Ok(None),
     }
 }
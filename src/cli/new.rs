@@ -1,24 +1,61 @@
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::cli::utils::PREVIEW_CMD;
-use crate::error::Result;
+use crate::cli::utils::{open_session, preview_command, same_directory, session_exists_for};
+use crate::config::{Config, RepoRoot};
+use crate::directories::{self, DirectorySource, Entry, ZoxideSource};
+use crate::error::{Result, TsmError};
 use crate::fzf::FzfPicker;
+use crate::git::{self, Repository, parse_remote_url};
+use crate::naming::NamingStrategy;
+use crate::paths::{contract_path, expand_path};
 use crate::tmux::TmuxClient;
 use crate::zoxide;
 
-/// Creates a new tmux session with optional directory selection via zoxide and fzf.
+/// Creates a new tmux session with optional directory selection via fzf.
 ///
-/// If a session with the specified name already exists, switches to it instead of creating a new one.
+/// If a session is already rooted in the selected directory, or a session with the specified
+/// name exists, switches to it instead of creating a new one. Derived names that clash with a
+/// session rooted elsewhere are disambiguated with the parent directory.
 #[derive(clap::Parser, Debug)]
 pub struct NewCommand {
+    /// Keywords passed to `zoxide query`; a single match is opened without a picker
+    #[clap(conflicts_with_all = ["path", "clone"])]
+    keywords: Vec<String>,
+
+    /// Open the best zoxide match without a picker
+    #[clap(short, long, default_value_t = false, conflicts_with_all = ["path", "clone"])]
+    first: bool,
+
     /// Session name (auto-generated if not provided)
     #[clap(short, long)]
     name: Option<String>,
 
-    /// Directory path (skips zoxide if provided)
+    /// Directory path (skips the picker if provided)
     #[clap(short, long)]
     path: Option<String>,
 
+    /// Git URL to clone into the configured workspace layout and open
+    #[clap(short, long, conflicts_with = "path")]
+    clone: Option<String>,
+
+    /// Session naming strategy: basename, parent, git-repo, git-remote, repo-branch
+    /// or a template such as "{repo}-{branch}" (overrides the config file)
+    #[clap(short = 'N', long)]
+    naming: Option<NamingStrategy>,
+
+    /// Use the enclosing git repository root instead of the selected subdirectory
+    #[clap(short = 'r', long, default_value_t = false, conflicts_with = "here")]
+    repo_root: bool,
+
+    /// Use the selected directory even if it is inside a git repository
+    #[clap(long, default_value_t = false)]
+    here: bool,
+
+    /// List directories that already have a session first
+    #[clap(short, long, default_value_t = false)]
+    sessions_first: bool,
+
     /// Show directory preview in fzf
     #[clap(short = 'v', long, default_value_t = false)]
     preview: bool,
@@ -35,87 +72,249 @@ pub struct NewCommand {
 impl NewCommand {
     /// Executes the new session command.
     ///
-    /// Creates a new tmux session or switches to an existing one with the same name.
-    /// If no path is provided, prompts the user to select a directory using zoxide and fzf.
+    /// Creates a new tmux session or switches to an existing one for the same directory or name.
+    /// If no path is provided, prompts the user to select a directory from the configured
+    /// sources using fzf.
     pub fn run(&self, client: &TmuxClient) -> Result<()> {
-        let path = if let Some(p) = self.path.clone() {
-            p
+        let config = Config::load()?;
+
+        let (path, picked) = if let Some(url) = &self.clone {
+            (clone_repository(url, &config.clone.path)?, false)
+        } else if let Some(p) = self.path.clone() {
+            (p, false)
         } else {
-            let dirs = zoxide::query_directories()?;
+            let jump = self.first || !self.keywords.is_empty();
 
-            let preview_cmd = if self.preview { PREVIEW_CMD } else { "" };
+            let entries = if jump {
+                ZoxideSource {
+                    keywords: self.keywords.clone(),
+                }
+                .directories()?
+            } else {
+                let sources = directories::configured_sources(&config);
+                directories::collect(&sources, &config.directories.ignore)?
+            };
 
-            let picker = FzfPicker::new()
-                .with_prompt(&self.prompt)
-                .with_preview_command(preview_cmd);
+            if jump && entries.is_empty() {
+                return Err(TsmError::InvalidArgument(format!(
+                    "No zoxide match for '{}'",
+                    self.keywords.join(" ")
+                )));
+            }
+
+            if jump && (self.first || entries.len() == 1) {
+                (entries[0].path.to_string_lossy().to_string(), false)
+            } else {
+                let sessions_first = self.sessions_first || config.directories.sessions_first;
+                let entries = mark_sessions(client, entries, sessions_first);
 
-            match picker.pick(&dirs)? {
-                Some(selection) => selection,
-                None => return Ok(()),
+                match self.pick_directory(&entries)? {
+                    Some(path) => (path, true),
+                    None => return Ok(()),
+                }
             }
         };
 
-        let expanded_path = if path.starts_with('~') {
-            std::env::home_dir()
-                .map(|home| path.replacen('~', &home.to_string_lossy(), 1))
-                .unwrap_or(path)
-        } else if path == "." {
-            std::env::current_dir()
-                .map(|cwd| cwd.to_string_lossy().to_string())
-                .unwrap_or(path)
+        let expanded_path = self.choose_root(expand_path(&path), &config, picked)?;
+        let Some(expanded_path) = expanded_path else {
+            return Ok(());
+        };
+
+        let strategy = self.naming.clone().unwrap_or(config.session.naming);
+
+        // Teach zoxide about directories opened some other way. Done first, as attaching
+        // outside tmux blocks until the client detaches
+        if !session_exists_for(client, &expanded_path, self.name.as_deref(), &strategy)
+            && !zoxide_knows(&expanded_path)
+        {
+            let _ = zoxide::add_directory(&expanded_path);
+        }
+
+        open_session(
+            client,
+            &expanded_path,
+            self.name.as_deref(),
+            &strategy,
+            self.quiet,
+        )
+    }
+}
+
+impl NewCommand {
+    /// Lets the user pick one of `entries` in fzf, returning its path.
+    fn pick_directory(&self, entries: &[Entry]) -> Result<Option<String>> {
+        let items: Vec<String> = entries.iter().map(picker_item).collect();
+
+        let preview_cmd = if self.preview {
+            preview_command("directory -- {1}")
         } else {
-            path
+            String::new()
         };
 
-        let name = if let Some(n) = self.name.clone() {
-            sanitise_session_name(&n)
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(&preview_cmd)
+            .with_delimiter("\t");
+
+        Ok(picker
+            .pick(&items)?
+            .map(|selection| selection.split('\t').next().unwrap_or_default().to_string()))
+    }
+
+    /// Decides whether to root the session at `path` or its git repository root.
+    ///
+    /// Only asks when `picked` says the directory was chosen interactively. Returns `None`
+    /// if the user cancelled the choice.
+    fn choose_root(&self, path: String, config: &Config, picked: bool) -> Result<Option<String>> {
+        let Some(repo) = Repository::discover(Path::new(&path)) else {
+            return Ok(Some(path));
+        };
+
+        if same_directory(&repo.root.to_string_lossy(), &path) {
+            return Ok(Some(path));
+        }
+
+        let mode = if self.repo_root {
+            RepoRoot::Always
+        } else if self.here {
+            RepoRoot::Never
         } else {
-            match Path::new(&expanded_path).file_name() {
-                Some(os_str) => sanitise_session_name(&os_str.to_string_lossy()),
-                None => "_".to_string(),
-            }
+            config.session.repo_root
         };
 
-        let sessions = client.list_sessions();
-        if sessions.contains(&name) {
-            if client.is_inside_tmux() {
-                client.switch_session(&name)?;
-            } else {
-                client.attach_session(&name)?;
-            }
+        let root = repo.root.to_string_lossy().to_string();
 
-            if !self.quiet {
-                client.display_message(&format!(
-                    "{} session already exists. Switching to it.",
-                    name
-                ))?;
+        match mode {
+            RepoRoot::Always => Ok(Some(root)),
+            RepoRoot::Never => Ok(Some(path)),
+            RepoRoot::Ask if !picked => Ok(Some(path)),
+            RepoRoot::Ask => {
+                let repo_item = format!("repo root\t{}", root);
+                let here_item = format!("this directory\t{}", path);
+
+                let picker = FzfPicker::new()
+                    .with_prompt(&format!("{} is inside {}: ", path, repo.name()))
+                    .with_delimiter("\t");
+
+                match picker.pick(&[repo_item.clone(), here_item])? {
+                    Some(selection) if selection == repo_item => Ok(Some(root)),
+                    Some(_) => Ok(Some(path)),
+                    None => Ok(None),
+                }
             }
-            return Ok(());
         }
+    }
+}
 
-        client.new_session(name.clone(), expanded_path)?;
+/// Labels entries with the sessions rooted in them, optionally moving those entries first.
+fn mark_sessions(client: &TmuxClient, entries: Vec<Entry>, sessions_first: bool) -> Vec<Entry> {
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
 
-        if !self.quiet {
-            client.display_message(&format!("Created new session '{}'", name))?;
-        }
-        Ok(())
+    let sessions: Vec<(String, PathBuf)> = client
+        .list_session_paths()
+        .into_iter()
+        .map(|(name, path)| (name, canonical(Path::new(&path))))
+        .collect();
+
+    let mut marked: Vec<(Entry, bool)> = entries
+        .into_iter()
+        .map(|mut entry| {
+            let path = canonical(&entry.path);
+            let labels: Vec<String> = sessions
+                .iter()
+                .filter(|(_, p)| *p == path)
+                .map(|(name, _)| format!("● {}", name))
+                .collect();
+            let has_session = !labels.is_empty();
+
+            entry.labels.splice(0..0, labels);
+            (entry, has_session)
+        })
+        .collect();
+
+    if sessions_first {
+        marked.sort_by_key(|(_, has_session)| !has_session);
     }
+
+    marked.into_iter().map(|(entry, _)| entry).collect()
 }
 
-fn sanitise_session_name(name: &str) -> String {
-    let mut name = name;
+/// Formats an entry as its path, followed by its labels in a second tab-separated field.
+fn picker_item(entry: &Entry) -> String {
+    let path = contract_path(&entry.path);
 
-    if name.starts_with('.') {
-        name = name.trim_start_matches(".");
+    if entry.labels.is_empty() {
+        return path;
     }
 
-    name.chars()
-        .map(|c| {
-            if c.is_whitespace() || c == '.' {
-                '_'
-            } else {
-                c
-            }
+    let labels: Vec<String> = entry.labels.iter().map(|l| format!("[{}]", l)).collect();
+    format!("{}\t{}", path, labels.join(" "))
+}
+
+/// Directory `url` is checked out in, from a template using `{host}`, `{owner}` and `{repo}`.
+fn checkout_path(url: &str, template: &str) -> Result<String> {
+    let slug = parse_remote_url(url)
+        .ok_or_else(|| TsmError::InvalidArgument(format!("Invalid git URL '{}'", url)))?;
+
+    Ok(expand_path(
+        &template
+            .replace("{host}", &slug.host)
+            .replace("{owner}", &slug.owner)
+            .replace("{repo}", &slug.repo),
+    ))
+}
+
+/// Whether `path` is already in the zoxide database, so adding it would only bump its
+/// rank. Also true when zoxide cannot be queried, as there is nothing to add it to.
+fn zoxide_knows(path: &str) -> bool {
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+
+    zoxide::query_directories(&[])
+        .map(|dirs| {
+            dirs.iter()
+                .any(|dir| dir.path == Path::new(path) || dir.path == canonical)
         })
-        .collect()
+        .unwrap_or(true)
+}
+
+/// Clones `url` into the workspace layout unless it is already checked out there.
+///
+/// Returns the checkout directory.
+fn clone_repository(url: &str, template: &str) -> Result<String> {
+    let dest = checkout_path(url, template)?;
+
+    if Repository::open(Path::new(&dest)).is_none() {
+        git::clone(url, Path::new(&dest))?;
+    }
+
+    // Not having zoxide installed should not prevent opening the checkout
+    let _ = zoxide::add_directory(&dest);
+
+    Ok(dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "/src/{host}/{owner}/{repo}";
+
+    #[test]
+    fn checkout_path_for_each_url_style() {
+        for url in [
+            "https://github.com/ionut-t/tsm.git",
+            "ssh://git@github.com/ionut-t/tsm",
+            "git@github.com:ionut-t/tsm.git",
+        ] {
+            assert_eq!(
+                checkout_path(url, TEMPLATE).ok().as_deref(),
+                Some("/src/github.com/ionut-t/tsm")
+            );
+        }
+    }
+
+    #[test]
+    fn checkout_path_rejects_urls_without_a_repository() {
+        assert!(checkout_path("https://github.com/", TEMPLATE).is_err());
+    }
 }
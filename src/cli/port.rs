@@ -0,0 +1,227 @@
+use crate::cli::utils::{PREVIEW_CMD, switch_to_pane};
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::{PaneHistory, WindowHistory};
+use crate::paths;
+use crate::process::{self, ProcessTable};
+use crate::tmux::{Pane, TmuxClient};
+
+/// Finds the pane whose process listens on a TCP port and switches to it.
+///
+/// Listening sockets are read from `/proc/net/tcp{,6}` and traced to the processes
+/// holding them, then up the process tree to a pane's shell.
+#[derive(clap::Parser, Debug)]
+pub struct PortCommand {
+    /// Port number
+    #[clap(required_unless_present = "list")]
+    port: Option<u16>,
+
+    /// Pick from the listening ports of every pane
+    #[clap(short, long, default_value_t = false, conflicts_with_all = ["port", "kill", "kill_window"])]
+    list: bool,
+
+    /// Kill the processes listening on the port instead of switching
+    #[clap(short, long, default_value_t = false, conflicts_with = "kill_window")]
+    kill: bool,
+
+    /// With --kill, also kill listening processes that were not started from a pane
+    #[clap(short, long, default_value_t = false, requires = "kill")]
+    force: bool,
+
+    /// Kill the window of the pane owning the port instead of switching
+    #[clap(short = 'K', long, default_value_t = false)]
+    kill_window: bool,
+
+    /// No success message
+    #[clap(short = 'q', long, default_value_t = false)]
+    quiet: bool,
+
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Port: ")]
+    prompt: String,
+}
+
+/// A process listening on a port, and the pane it was started from
+struct Owner<'a> {
+    port: u16,
+    pid: u32,
+    pane: Option<&'a Pane>,
+}
+
+impl PortCommand {
+    /// Executes the port command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let panes = client.list_panes();
+        let owners = port_owners(&panes);
+
+        let pane = match self.port {
+            Some(port) => self.find_pane(client, port, &owners)?,
+            None => self.pick(client, &owners)?,
+        };
+        let Some(pane) = pane else {
+            return Ok(());
+        };
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        let mut pane_history =
+            PaneHistory::new(paths::pane_history_file_path(), client.server_id());
+        pane_history.load()?;
+        switch_to_pane(client, pane, &mut history, &mut pane_history)
+    }
+
+    /// Pane to switch to for `port`, after handling `--kill` and `--kill-window`.
+    fn find_pane<'a>(
+        &self,
+        client: &TmuxClient,
+        port: u16,
+        owners: &[Owner<'a>],
+    ) -> Result<Option<&'a Pane>> {
+        let owners: Vec<&Owner> = owners.iter().filter(|o| o.port == port).collect();
+
+        let Some(first) = owners.first() else {
+            client.display_message(&format!("Nothing is listening on port {}", port))?;
+            return Ok(None);
+        };
+
+        if self.kill {
+            let targets: Vec<u32> = owners
+                .iter()
+                .filter(|o| self.force || o.pane.is_some())
+                .map(|o| o.pid)
+                .collect();
+
+            if targets.is_empty() {
+                client.display_message(&format!(
+                    "Port {} is used by {}, which is not running in tmux; use --force to kill it",
+                    port,
+                    describe(first.pid)
+                ))?;
+                return Ok(None);
+            }
+
+            // Describe before killing, the command line is gone afterwards
+            let killed: Vec<String> = targets.iter().map(|pid| describe(*pid)).collect();
+            for pid in targets {
+                process::terminate(pid)?;
+            }
+
+            if !self.quiet {
+                client.display_message(&format!(
+                    "Killed {} on port {}",
+                    killed.join(", "),
+                    port
+                ))?;
+            }
+            return Ok(None);
+        }
+
+        let Some(pane) = owners.iter().find_map(|o| o.pane) else {
+            client.display_message(&format!(
+                "Port {} is used by {}, which is not running in tmux",
+                port,
+                describe(first.pid)
+            ))?;
+            return Ok(None);
+        };
+
+        if self.kill_window {
+            client.kill_window(&pane.window_id)?;
+            if !self.quiet {
+                client.display_message(&format!(
+                    "Killed window {}:{} listening on port {}",
+                    pane.session_name, pane.window_index, port
+                ))?;
+            }
+            return Ok(None);
+        }
+
+        Ok(Some(pane))
+    }
+
+    /// Lists the listening ports of every pane and returns the chosen pane.
+    fn pick<'a>(&self, client: &TmuxClient, owners: &[Owner<'a>]) -> Result<Option<&'a Pane>> {
+        let items: Vec<String> = owners
+            .iter()
+            .filter_map(|owner| {
+                let pane = owner.pane?;
+                Some(format!(
+                    "{}\t:{:<5}  {}:{}.{}  {}",
+                    pane.id,
+                    owner.port,
+                    pane.session_name,
+                    pane.window_index,
+                    pane.index,
+                    describe(owner.pid)
+                ))
+            })
+            .collect();
+
+        if items.is_empty() {
+            client.display_message("No pane is listening on a port")?;
+            return Ok(None);
+        }
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(PREVIEW_CMD)
+            .with_delimiter("\t")
+            .with_nth("2..");
+
+        let Some(selection) = picker.pick(&items)? else {
+            return Ok(None); // User canceled
+        };
+
+        let pane_id = selection.split('\t').next().unwrap_or_default();
+        owners
+            .iter()
+            .find_map(|owner| owner.pane.filter(|pane| pane.id == pane_id))
+            .map(Some)
+            .ok_or_else(|| {
+                TsmError::InvalidArgument(format!("Selected pane {} not found", pane_id))
+            })
+    }
+}
+
+/// Every process listening on a TCP port, sorted by port, with the pane whose shell
+/// it descends from.
+fn port_owners(panes: &[Pane]) -> Vec<Owner<'_>> {
+    let listeners = process::listening_sockets();
+    let inodes: Vec<u64> = listeners.iter().map(|l| l.inode).collect();
+    let sockets = process::socket_owners(&inodes);
+    let table = ProcessTable::load();
+
+    let mut owners: Vec<Owner> = Vec::new();
+    for listener in &listeners {
+        for &pid in sockets.get(&listener.inode).into_iter().flatten() {
+            if owners
+                .iter()
+                .any(|o| o.port == listener.port && o.pid == pid)
+            {
+                continue;
+            }
+
+            let pane = table
+                .ancestors(pid)
+                .into_iter()
+                .find_map(|ancestor| panes.iter().find(|pane| pane.pid == ancestor));
+
+            owners.push(Owner {
+                port: listener.port,
+                pid,
+                pane,
+            });
+        }
+    }
+
+    owners.sort_by_key(|o| (o.port, o.pid));
+    owners
+}
+
+/// Command line and PID of a process, for messages
+fn describe(pid: u32) -> String {
+    match process::command_line(pid) {
+        Some(command) => format!("{} ({})", command, pid),
+        None => format!("pid {}", pid),
+    }
+}
@@ -0,0 +1,411 @@
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli::utils::{same_directory, session_windows};
+use crate::config::{Config, Listing, PreviewConfig};
+use crate::error::Result;
+use crate::git::Repository;
+use crate::paths::expand_path;
+use crate::tmux::{Pane, TmuxClient};
+
+const MAX_ENTRIES: usize = 100;
+
+/// Commands that count as an idle pane rather than a running program
+const SHELLS: &[&str] = &[
+    "bash", "zsh", "fish", "sh", "dash", "ksh", "tcsh", "csh", "nu", "elvish", "xonsh",
+];
+
+/// Files whose presence identifies a kind of project
+const PROJECT_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "Rust"),
+    ("go.mod", "Go"),
+    ("tsconfig.json", "TypeScript"),
+    ("package.json", "Node.js"),
+    ("deno.json", "Deno"),
+    ("pyproject.toml", "Python"),
+    ("setup.py", "Python"),
+    ("requirements.txt", "Python"),
+    ("Gemfile", "Ruby"),
+    ("composer.json", "PHP"),
+    ("pom.xml", "Java"),
+    ("build.gradle", "Java"),
+    ("build.gradle.kts", "Kotlin"),
+    ("mix.exs", "Elixir"),
+    ("Package.swift", "Swift"),
+    ("pubspec.yaml", "Dart"),
+    ("build.zig", "Zig"),
+    ("stack.yaml", "Haskell"),
+    ("dune-project", "OCaml"),
+    ("CMakeLists.txt", "C/C++"),
+    ("meson.build", "C/C++"),
+    ("flake.nix", "Nix"),
+    ("default.nix", "Nix"),
+    ("Makefile", "Make"),
+    ("Dockerfile", "Docker"),
+];
+
+/// Renders previews for fzf pickers.
+///
+/// Used as the `--preview` command of tsm's own pickers.
+#[derive(clap::Parser, Debug)]
+pub struct PreviewCommand {
+    #[clap(subcommand)]
+    target: PreviewTarget,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum PreviewTarget {
+    /// Preview a directory: git status, project type, README and contents, or the
+    /// session rooted there
+    Directory {
+        /// Directory path, `~` is expanded
+        path: String,
+    },
+
+    /// Preview a session: its windows, running commands and active pane
+    Session {
+        /// Session name
+        name: String,
+    },
+
+    /// Preview the contents of a pane
+    Pane {
+        /// Pane ID
+        id: String,
+
+        /// Centre the preview on this line, counted from the top of the scrollback
+        #[clap(long)]
+        line: Option<u32>,
+    },
+}
+
+impl PreviewCommand {
+    /// Executes the preview command, printing the preview to stdout.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let mut out = String::new();
+
+        match &self.target {
+            PreviewTarget::Directory { path } => {
+                let path = expand_path(path);
+
+                let session = client
+                    .list_session_paths()
+                    .into_iter()
+                    .find(|(_, session_path)| same_directory(session_path, &path));
+
+                match session {
+                    Some((name, _)) => preview_session(&mut out, client, &name)?,
+                    None => {
+                        let config = Config::load()?;
+                        preview_directory(&mut out, Path::new(&path), &config.preview)?
+                    }
+                }
+            }
+            PreviewTarget::Session { name } => preview_session(&mut out, client, name)?,
+            PreviewTarget::Pane { id, line } => preview_pane(&mut out, client, id, *line)?,
+        }
+
+        // fzf closes the pipe as soon as the preview is replaced
+        let _ = io::stdout().write_all(out.as_bytes());
+        Ok(())
+    }
+}
+
+/// Lists the windows of a session with their pane counts, marking the active one, then
+/// the commands running in other windows and the contents of the active pane.
+fn preview_session(out: &mut String, client: &TmuxClient, session: &str) -> Result<()> {
+    let active = client.active_window_id(session).unwrap_or_default();
+    let windows = session_windows(client, session);
+    let panes: Vec<Pane> = client
+        .list_panes()
+        .into_iter()
+        .filter(|pane| pane.session_name == session)
+        .collect();
+
+    let _ = writeln!(
+        out,
+        "\x1b[1;32m● {}\x1b[0m  {} windows",
+        session,
+        windows.len()
+    );
+
+    for window in &windows {
+        let pane_count = panes.iter().filter(|p| p.window_id == window.id).count();
+        let panes_label = if pane_count == 1 { "pane" } else { "panes" };
+
+        if window.id == active {
+            let _ = writeln!(
+                out,
+                "\x1b[1m* {}: {}\x1b[0m \x1b[2m({} {})\x1b[0m",
+                window.index, window.name, pane_count, panes_label
+            );
+        } else {
+            let _ = writeln!(
+                out,
+                "  {}: {} \x1b[2m({} {})\x1b[0m",
+                window.index, window.name, pane_count, panes_label
+            );
+        }
+    }
+
+    let running: Vec<&Pane> = panes
+        .iter()
+        .filter(|pane| pane.window_id != active && !SHELLS.contains(&pane.command.as_str()))
+        .collect();
+
+    if !running.is_empty() {
+        let _ = writeln!(out, "\n\x1b[1mRunning\x1b[0m");
+        for pane in running {
+            let _ = writeln!(
+                out,
+                "  {}.{}: \x1b[33m{}\x1b[0m",
+                pane.window_index, pane.index, pane.command
+            );
+        }
+    }
+
+    out.push('\n');
+    out.push_str(&client.capture_pane(&format!("{}:", session))?);
+
+    Ok(())
+}
+
+/// Shows the visible contents of a pane, or the lines around `line` with that line marked.
+fn preview_pane(out: &mut String, client: &TmuxClient, id: &str, line: Option<u32>) -> Result<()> {
+    let Some(line) = line else {
+        out.push_str(&client.capture_pane(id)?);
+        return Ok(());
+    };
+
+    let height: i64 = env::var("FZF_PREVIEW_LINES")
+        .ok()
+        .and_then(|lines| lines.parse().ok())
+        .unwrap_or(40);
+    let history_size = client.history_size(id)? as i64;
+
+    // Lines relative to the top of the visible area, negative in the scrollback
+    let hit = line as i64 - history_size;
+    let start = (hit - height / 2).max(-history_size);
+    let contents = client.capture_pane_lines(id, start, start + height - 1)?;
+
+    for (i, text) in contents.lines().enumerate() {
+        if start + i as i64 == hit {
+            let _ = writeln!(out, "\x1b[1;33m▶\x1b[0m {}", text);
+        } else {
+            let _ = writeln!(out, "  {}", text);
+        }
+    }
+
+    Ok(())
+}
+
+fn preview_directory(out: &mut String, path: &Path, config: &PreviewConfig) -> Result<()> {
+    if let Some(repo) = Repository::discover(path) {
+        write_git_summary(out, &repo);
+    }
+
+    let types = project_types(path);
+    if !types.is_empty() {
+        let _ = writeln!(out, "\x1b[36m{}\x1b[0m", types.join(" · "));
+    }
+
+    if !out.is_empty() {
+        out.push('\n');
+    }
+
+    if config.readme_lines > 0
+        && let Some(readme) = find_readme(path)
+    {
+        write_readme(out, &readme, config);
+        out.push('\n');
+    }
+
+    if config.eza && write_eza_listing(out, path, config) {
+        return Ok(());
+    }
+
+    match config.listing {
+        Listing::Ls => write_listing(out, path)?,
+        Listing::Tree => {
+            let mut remaining = MAX_ENTRIES;
+            let _ = writeln!(out, "\x1b[1;34m{}\x1b[0m", path.display());
+            write_tree(out, path, "", config.tree_depth, &mut remaining);
+            if remaining == 0 {
+                let _ = writeln!(out, "…");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a directory, directories first, then files, each alphabetically.
+fn sorted_entries(path: &Path) -> io::Result<Vec<(bool, String)>> {
+    let mut entries: Vec<(bool, String)> = fs::read_dir(path)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            (is_dir, entry.file_name().to_string_lossy().to_string())
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| a.1.to_lowercase().cmp(&b.1.to_lowercase()))
+    });
+
+    Ok(entries)
+}
+
+fn write_listing(out: &mut String, path: &Path) -> Result<()> {
+    let entries = sorted_entries(path)?;
+
+    for (is_dir, name) in entries.iter().take(MAX_ENTRIES) {
+        if *is_dir {
+            let _ = writeln!(out, "\x1b[1;34m{}/\x1b[0m", name);
+        } else {
+            let _ = writeln!(out, "{}", name);
+        }
+    }
+
+    if entries.len() > MAX_ENTRIES {
+        let _ = writeln!(out, "… {} more", entries.len() - MAX_ENTRIES);
+    }
+
+    Ok(())
+}
+
+/// Draws the non-hidden contents of `path` down to `depth` levels, at most `remaining` lines.
+fn write_tree(out: &mut String, path: &Path, prefix: &str, depth: usize, remaining: &mut usize) {
+    let Ok(entries) = sorted_entries(path) else {
+        return;
+    };
+
+    let entries: Vec<_> = entries
+        .into_iter()
+        .filter(|(_, name)| !name.starts_with('.'))
+        .collect();
+
+    for (i, (is_dir, name)) in entries.iter().enumerate() {
+        if *remaining == 0 {
+            return;
+        }
+        *remaining -= 1;
+
+        let last = i + 1 == entries.len();
+        let branch = if last { "└── " } else { "├── " };
+
+        if *is_dir {
+            let _ = writeln!(out, "{}{}\x1b[1;34m{}/\x1b[0m", prefix, branch, name);
+            if depth > 1 {
+                let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+                write_tree(out, &path.join(name), &child_prefix, depth - 1, remaining);
+            }
+        } else {
+            let _ = writeln!(out, "{}{}{}", prefix, branch, name);
+        }
+    }
+}
+
+/// Lists the directory with eza. Returns false if eza is missing or failed.
+fn write_eza_listing(out: &mut String, path: &Path, config: &PreviewConfig) -> bool {
+    let mut cmd = Command::new("eza");
+    cmd.args([
+        "--color=always",
+        "--icons=auto",
+        "--group-directories-first",
+    ]);
+
+    match config.listing {
+        Listing::Ls => cmd.arg("-1"),
+        Listing::Tree => cmd
+            .arg("--tree")
+            .arg(format!("--level={}", config.tree_depth)),
+    };
+
+    match cmd.arg(path).output() {
+        Ok(output) if output.status.success() => {
+            out.push_str(&String::from_utf8_lossy(&output.stdout));
+            true
+        }
+        _ => false,
+    }
+}
+
+fn find_readme(path: &Path) -> Option<PathBuf> {
+    let entries = sorted_entries(path).ok()?;
+
+    entries
+        .into_iter()
+        .filter(|(is_dir, name)| !is_dir && name.to_lowercase().starts_with("readme"))
+        .map(|(_, name)| path.join(name))
+        .next()
+}
+
+/// Prints the first lines of the README, highlighted by bat when it is available.
+fn write_readme(out: &mut String, readme: &Path, config: &PreviewConfig) {
+    if config.bat {
+        let output = Command::new("bat")
+            .args(["--color=always", "--style=header", "--paging=never"])
+            .arg(format!("--line-range=:{}", config.readme_lines))
+            .arg(readme)
+            .output();
+
+        if let Ok(output) = output
+            && output.status.success()
+        {
+            out.push_str(&String::from_utf8_lossy(&output.stdout));
+            return;
+        }
+    }
+
+    let Ok(contents) = fs::read_to_string(readme) else {
+        return;
+    };
+
+    let name = readme.file_name().unwrap_or_default().to_string_lossy();
+    let _ = writeln!(out, "\x1b[1m{}\x1b[0m", name);
+
+    for line in contents.lines().take(config.readme_lines) {
+        let _ = writeln!(out, "\x1b[2m{}\x1b[0m", line);
+    }
+}
+
+/// Guesses the languages and tools of a project from the files at its root.
+fn project_types(path: &Path) -> Vec<&'static str> {
+    let mut types: Vec<&'static str> = Vec::new();
+
+    for (file, kind) in PROJECT_MARKERS {
+        if path.join(file).exists() && !types.contains(kind) {
+            types.push(kind);
+        }
+    }
+
+    types
+}
+
+fn write_git_summary(out: &mut String, repo: &Repository) {
+    let head = repo.head().unwrap_or_else(|| "unknown".to_string());
+
+    let status = match repo.changed_files() {
+        Some(0) => "\x1b[32m✓ clean\x1b[0m".to_string(),
+        Some(n) => format!("\x1b[33m● {} changed\x1b[0m", n),
+        None => String::new(),
+    };
+
+    let _ = writeln!(
+        out,
+        "\x1b[1;35m {}\x1b[0m  {}  {}",
+        repo.name(),
+        head,
+        status
+    );
+
+    if let Some(commit) = repo.last_commit() {
+        let _ = writeln!(out, "\x1b[2m{}\x1b[0m", commit);
+    }
+}
@@ -1,10 +1,20 @@
-use crate::{error::Result, history::WindowHistory, paths, tmux::TmuxClient};
+use crate::{
+    error::Result,
+    history::{Cycle, JumpList, PaneHistory, WindowHistory},
+    paths,
+    tmux::TmuxClient,
+};
 
-/// Records the current window access in the history file.
+/// Records the current window access in the history file and the jumplist, and the
+/// focused pane in the pane history.
 ///
 /// This command is typically used in tmux hooks to track window access times.
 #[derive(clap::Parser, Debug)]
-pub struct RecordCommand;
+pub struct RecordCommand {
+    /// Only record the focused pane (for the `after-select-pane` hook)
+    #[clap(short, long, default_value_t = false)]
+    pane: bool,
+}
 
 impl RecordCommand {
     /// Executes the record command.
@@ -15,6 +25,21 @@ impl RecordCommand {
             return Ok(());
         }
 
+        // Windows passed through by `tsm cycle` are recorded when the walk ends
+        let mut cycle = Cycle::new(paths::cycle_file_path());
+        if cycle.load()? && cycle.is_active() {
+            return Ok(());
+        }
+
+        let mut panes = PaneHistory::new(paths::pane_history_file_path(), client.server_id());
+        panes.load()?;
+        panes.record(&client.current_pane()?.id);
+        panes.save()?;
+
+        if self.pane {
+            return Ok(());
+        }
+
         let (session, window) = client.get_current_window()?;
 
         let mut history = WindowHistory::new(paths::history_file_path());
@@ -22,6 +47,11 @@ impl RecordCommand {
         history.record_access(&session, window);
         history.save()?;
 
+        let mut jumps = JumpList::new(paths::jumplist_file_path(), client.server_id());
+        jumps.load()?;
+        jumps.record(&client.current_window_id()?);
+        jumps.save()?;
+
         Ok(())
     }
 }
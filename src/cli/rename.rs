@@ -1,3 +1,6 @@
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_sessions;
 use crate::error::Result;
 use crate::tmux::TmuxClient;
 
@@ -7,7 +10,7 @@ use crate::tmux::TmuxClient;
 #[derive(clap::Parser, Debug)]
 pub struct RenameCommand {
     /// Current name - defaults to the active session if not provided
-    #[clap(short = 'c', long)]
+    #[clap(short = 'c', long, add = ArgValueCandidates::new(complete_sessions))]
     current_name: Option<String>,
     /// New name
     #[clap(short = 'n', long)]
@@ -0,0 +1,43 @@
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_sessions;
+use crate::cli::utils::{migrate_history, resolve_session, session_windows};
+use crate::error::Result;
+use crate::history::WindowHistory;
+use crate::paths;
+use crate::tmux::TmuxClient;
+
+/// Renumbers the windows of a session to close gaps between indices.
+///
+/// Window history is updated so recorded accesses follow the windows to their new indices.
+#[derive(clap::Parser, Debug)]
+pub struct RenumberCommand {
+    /// Session name (defaults to the current session)
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
+    session: Option<String>,
+
+    /// No success message
+    #[clap(short = 'q', long, default_value_t = false)]
+    quiet: bool,
+}
+
+impl RenumberCommand {
+    /// Executes the renumber command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let session = resolve_session(client, self.session.as_deref())?;
+        let before = session_windows(client, &session);
+
+        client.renumber_windows(&session)?;
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        migrate_history(client, &before, &mut history);
+        history.save()?;
+
+        if !self.quiet {
+            client.display_message(&format!("Renumbered windows in session {}", session))?;
+        }
+
+        Ok(())
+    }
+}
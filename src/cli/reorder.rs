@@ -0,0 +1,106 @@
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_sessions;
+use crate::cli::utils::{
+    apply_window_order, edit_in_editor, migrate_history, resolve_session, session_windows,
+};
+use crate::error::Result;
+use crate::history::WindowHistory;
+use crate::paths;
+use crate::tmux::{TmuxClient, Window};
+
+const INSTRUCTIONS: &str = "\
+# Reorder the lines above to rearrange the windows.
+# The first column is the window ID; the rest is informational.
+# Removed lines keep their relative order at the end.
+# Lines starting with '#' are ignored. Leave the file unchanged to abort.
+";
+
+/// Reorders the windows of a session in a text editor.
+///
+/// Opens `$VISUAL`/`$EDITOR` with one line per window, similar to an interactive rebase.
+/// Saving the file applies the new order; window history follows the windows to their new indices.
+#[derive(clap::Parser, Debug)]
+pub struct ReorderCommand {
+    /// Session name (defaults to the current session)
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
+    session: Option<String>,
+
+    /// No success message
+    #[clap(short = 'q', long, default_value_t = false)]
+    quiet: bool,
+}
+
+impl ReorderCommand {
+    /// Executes the reorder command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let session = resolve_session(client, self.session.as_deref())?;
+        let windows = session_windows(client, &session);
+
+        if windows.len() < 2 {
+            client.display_message("Not enough windows in the session to reorder")?;
+            return Ok(());
+        }
+
+        let order = match edit_order(&windows)? {
+            Some(order) => order,
+            None => {
+                if !self.quiet {
+                    client.display_message("Window order unchanged")?;
+                }
+                return Ok(());
+            }
+        };
+
+        apply_window_order(client, &session, &order)?;
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        migrate_history(client, &windows, &mut history);
+        history.save()?;
+
+        if !self.quiet {
+            client.display_message(&format!("Reordered windows in session {}", session))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Let the user edit the window order, returning `None` if it did not change.
+fn edit_order(windows: &[Window]) -> Result<Option<Vec<Window>>> {
+    let mut contents = String::new();
+    for w in windows {
+        contents.push_str(&format!("{}\t{}\t{}\n", w.id, w.index, w.name));
+    }
+    contents.push('\n');
+    contents.push_str(INSTRUCTIONS);
+
+    let edited = edit_in_editor("reorder", &contents)?;
+    if edited == contents {
+        return Ok(None);
+    }
+
+    let mut order: Vec<Window> = Vec::with_capacity(windows.len());
+    for line in edited.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let id = line.split_whitespace().next().unwrap_or_default();
+        if let Some(window) = windows.iter().find(|w| w.id == id)
+            && !order.iter().any(|w| w.id == id)
+        {
+            order.push(window.clone());
+        }
+    }
+
+    for window in windows {
+        if !order.iter().any(|w| w.id == window.id) {
+            order.push(window.clone());
+        }
+    }
+
+    Ok(Some(order))
+}
@@ -0,0 +1,92 @@
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_sessions;
+use crate::cli::utils::{
+    apply_window_order, migrate_history, resolve_session, session_windows, sort_windows_by_history,
+};
+use crate::error::Result;
+use crate::history::WindowHistory;
+use crate::paths;
+use crate::tmux::TmuxClient;
+
+/// Criteria for ordering windows
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum SortKey {
+    /// Alphabetically by window name
+    Name,
+    /// Most recently accessed first
+    History,
+    /// Oldest window first
+    Created,
+}
+
+/// Sorts the windows of a session.
+///
+/// Windows keep the indices the session already uses; only their order changes.
+#[derive(clap::Parser, Debug)]
+pub struct SortWindowsCommand {
+    /// Sort criteria
+    #[clap(short, long, value_enum, default_value_t = SortKey::Name)]
+    by: SortKey,
+
+    /// Reverse the order
+    #[clap(short, long, default_value_t = false)]
+    reverse: bool,
+
+    /// Session name (defaults to the current session)
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
+    session: Option<String>,
+
+    /// No success message
+    #[clap(short = 'q', long, default_value_t = false)]
+    quiet: bool,
+}
+
+impl SortWindowsCommand {
+    /// Executes the sort windows command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let session = resolve_session(client, self.session.as_deref())?;
+        let windows = session_windows(client, &session);
+
+        if windows.len() < 2 {
+            client.display_message("Not enough windows in the session to sort")?;
+            return Ok(());
+        }
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+
+        let mut order = match self.by {
+            SortKey::Name => {
+                let mut order = windows.clone();
+                order.sort_by_key(|w| w.name.to_lowercase());
+                order
+            }
+            SortKey::History => sort_windows_by_history(windows.clone(), &history)
+                .into_iter()
+                .map(|(w, _)| w)
+                .collect(),
+            SortKey::Created => {
+                // Window IDs are assigned sequentially as windows are created
+                let mut order = windows.clone();
+                order.sort_by_key(|w| w.id.trim_start_matches('@').parse::<u32>().unwrap_or(0));
+                order
+            }
+        };
+
+        if self.reverse {
+            order.reverse();
+        }
+
+        apply_window_order(client, &session, &order)?;
+
+        migrate_history(client, &windows, &mut history);
+        history.save()?;
+
+        if !self.quiet {
+            client.display_message(&format!("Sorted windows in session {}", session))?;
+        }
+
+        Ok(())
+    }
+}
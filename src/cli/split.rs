@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_sessions;
+use crate::cli::utils::{
+    PREVIEW_CMD, migrate_history, prompt_input, resolve_session, session_windows,
+};
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::WindowHistory;
+use crate::naming::sanitise_session_name;
+use crate::paths;
+use crate::tmux::{TmuxClient, Window};
+
+/// Splits windows off a session into a new session.
+///
+/// Windows are picked with fzf multi-select and keep their order and names.
+/// The new session is rooted in the working directory of the first selected window.
+#[derive(clap::Parser, Debug)]
+pub struct SplitCommand {
+    /// Name of the new session (prompts if not provided)
+    #[clap(short, long)]
+    name: Option<String>,
+
+    /// Session to split (defaults to the current session)
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
+    session: Option<String>,
+
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Select windows to split off: ")]
+    prompt: String,
+
+    /// No success message
+    #[clap(short = 'q', long, default_value_t = false)]
+    quiet: bool,
+}
+
+impl SplitCommand {
+    /// Executes the split command.
+    ///
+    /// Moves the selected windows into a new session and switches to it.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let session = resolve_session(client, self.session.as_deref())?;
+        let windows = session_windows(client, &session);
+
+        let selected = self.pick_windows(&windows)?;
+        let Some(first) = selected.first() else {
+            return Ok(());
+        };
+
+        let path = client.get_pane_current_path(&session, first.index)?;
+
+        let name = match &self.name {
+            Some(name) => sanitise_session_name(name),
+            None => {
+                let default_name = Path::new(&path)
+                    .file_name()
+                    .map(|name| sanitise_session_name(&name.to_string_lossy()))
+                    .unwrap_or_else(|| "_".to_string());
+
+                match prompt_input("New session name", &default_name)? {
+                    Some(name) => sanitise_session_name(&name),
+                    None => return Ok(()),
+                }
+            }
+        };
+
+        if client.has_session(&name) {
+            return Err(TsmError::InvalidArgument(format!(
+                "Session '{}' already exists",
+                name
+            )));
+        }
+
+        let placeholder = client.new_detached_session(&name, &path)?;
+
+        if selected.len() == windows.len()
+            && client.is_inside_tmux()
+            && client.current_session().ok().as_deref() == Some(session.as_str())
+        {
+            client.switch_session(&name)?;
+        }
+
+        client.replace_window(&session, first.index, &placeholder)?;
+        for window in &selected[1..] {
+            client.move_window(&session, window.index, &name)?;
+        }
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        migrate_history(client, &selected, &mut history);
+
+        let (new_session, window_index) = client.find_window_by_pane_id(&first.pane_id)?;
+        history.record_access(&new_session, window_index);
+        history.save()?;
+
+        if client.is_inside_tmux() {
+            client.switch_to_window(&new_session, window_index)?;
+        } else {
+            client.attach_to_window(&new_session, window_index)?;
+        }
+
+        if !self.quiet {
+            client.display_message(&format!(
+                "Moved {} windows from {} into new session {}",
+                selected.len(),
+                session,
+                name
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Prompts for the windows to split off, returned in session order.
+    fn pick_windows(&self, windows: &[Window]) -> Result<Vec<Window>> {
+        let items: Vec<String> = windows
+            .iter()
+            .map(|w| format!("{}\t{}: {}", w.pane_id, w.index, w.name))
+            .collect();
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(PREVIEW_CMD)
+            .with_delimiter("\t")
+            .with_nth("2..")
+            .with_multi();
+
+        let selected_ids: Vec<String> = picker
+            .pick_multi(&items)?
+            .iter()
+            .filter_map(|selection| selection.split('\t').next())
+            .map(|pane_id| pane_id.to_string())
+            .collect();
+
+        Ok(windows
+            .iter()
+            .filter(|w| selected_ids.contains(&w.pane_id))
+            .cloned()
+            .collect())
+    }
+}
@@ -1,22 +1,46 @@
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_current_window_indices;
+use crate::cli::utils::PREVIEW_CMD;
 use crate::error::Result;
 use crate::error::TsmError;
+use crate::fzf::FzfPicker;
 use crate::history::WindowHistory;
 use crate::paths;
-use crate::tmux::TmuxClient;
+use crate::tmux::{TmuxClient, Window};
 
 /// Swaps the positions of two windows within the current session.
 ///
 /// The source window defaults to the current window if not specified.
+/// The target is given by index, as a relative `--left`/`--right` move,
+/// or picked interactively via fzf when omitted.
 /// Both windows must be in the same session.
 #[derive(clap::Parser, Debug)]
 pub struct SwapWindowCommand {
     /// Source window index (defaults to the current window)
-    #[clap(short, long)]
+    #[clap(short, long, add = ArgValueCandidates::new(complete_current_window_indices))]
     source: Option<u32>,
 
-    /// Target window index
-    #[clap(short, long)]
-    target: u32,
+    /// Target window index (prompts for selection if not provided)
+    #[clap(
+        short,
+        long,
+        conflicts_with_all = ["left", "right"],
+        add = ArgValueCandidates::new(complete_current_window_indices)
+    )]
+    target: Option<u32>,
+
+    /// Swap with the window to the left (wraps around)
+    #[clap(short, long, default_value_t = false, conflicts_with = "right")]
+    left: bool,
+
+    /// Swap with the window to the right (wraps around)
+    #[clap(short, long, default_value_t = false)]
+    right: bool,
+
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Swap with window: ")]
+    prompt: String,
 
     /// No success message
     #[clap(short = 'q', long, default_value_t = false)]
@@ -40,17 +64,11 @@ impl SwapWindowCommand {
             }
         };
 
-        if source_index == self.target {
-            client.display_message("Source and target window indices are the same")?;
-            return Ok(());
-        }
-
         let session = client.current_session()?;
-        let all_windows = client.list_windows();
-        let session_windows: Vec<_> = all_windows
+        let session_windows: Vec<Window> = client
+            .list_windows()
             .into_iter()
             .filter(|w| w.session_name == session)
-            .map(|w| w.index)
             .collect();
 
         if session_windows.len() < 2 {
@@ -58,42 +76,97 @@ impl SwapWindowCommand {
             return Ok(());
         }
 
-        if !session_windows.contains(&source_index) {
+        let Some(source_position) = session_windows.iter().position(|w| w.index == source_index)
+        else {
             client.display_message(&format!(
                 "Window {} not found in current session",
                 source_index
             ))?;
             return Ok(());
+        };
+
+        let target_index = if let Some(target) = self.target {
+            target
+        } else if self.left || self.right {
+            let count = session_windows.len();
+            let target_position = if self.left {
+                (source_position + count - 1) % count
+            } else {
+                (source_position + 1) % count
+            };
+            session_windows[target_position].index
+        } else {
+            match self.pick_target(&session_windows, source_index)? {
+                Some(index) => index,
+                None => return Ok(()),
+            }
+        };
+
+        if source_index == target_index {
+            client.display_message("Source and target window indices are the same")?;
+            return Ok(());
         }
 
-        if !session_windows.contains(&self.target) {
+        if !session_windows.iter().any(|w| w.index == target_index) {
             client.display_message(&format!(
                 "Window {} not found in current session",
-                self.target
+                target_index
             ))?;
             return Ok(());
         }
 
         let (_, current_window_index) = client.get_current_window()?;
 
-        client.swap_windows(source_index, self.target)?;
+        client.swap_windows(source_index, target_index)?;
 
         if source_index == current_window_index {
-            client.switch_to_window(&session, self.target)?;
+            client.switch_to_window(&session, target_index)?;
 
             let mut history = WindowHistory::new(paths::history_file_path());
             history.load()?;
-            history.record_access(&session, self.target);
+            history.record_access(&session, target_index);
             history.save()?;
         }
 
         if !self.quiet {
             client.display_message(&format!(
                 "Swapped windows {} and {}",
-                source_index, self.target,
+                source_index, target_index,
             ))?;
         }
 
         Ok(())
     }
+
+    /// Prompts for the window to swap with, excluding the source window.
+    fn pick_target(&self, windows: &[Window], source_index: u32) -> Result<Option<u32>> {
+        let items: Vec<String> = windows
+            .iter()
+            .filter(|w| w.index != source_index)
+            .map(|w| format!("{}\t{}: {}", w.pane_id, w.index, w.name))
+            .collect();
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(PREVIEW_CMD)
+            .with_delimiter("\t")
+            .with_nth("2..");
+
+        let selection = match picker.pick(&items)? {
+            Some(selection) => selection,
+            None => return Ok(None),
+        };
+
+        selection
+            .split('\t')
+            .nth(1)
+            .and_then(|s| s.split(':').next())
+            .and_then(|s| s.trim().parse::<u32>().ok())
+            .map(Some)
+            .ok_or_else(|| {
+                TsmError::InvalidArgument(
+                    "Failed to parse fzf selection for window index".to_string(),
+                )
+            })
+    }
 }
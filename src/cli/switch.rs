@@ -1,3 +1,7 @@
+use clap_complete::ArgValueCandidates;
+
+use crate::cli::completions::complete_sessions;
+use crate::cli::utils::preview_command;
 use crate::error::Result;
 use crate::fzf::FzfPicker;
 use crate::tmux::TmuxClient;
@@ -6,9 +10,13 @@ use crate::tmux::TmuxClient;
 #[derive(clap::Parser, Debug)]
 pub struct SwitchCommand {
     /// Name of the session to switch to
-    #[clap(short, long)]
+    #[clap(short, long, add = ArgValueCandidates::new(complete_sessions))]
     name: Option<String>,
 
+    /// Show session preview in fzf
+    #[clap(short = 'v', long, default_value_t = false)]
+    preview: bool,
+
     /// fzf prompt
     #[clap(short = 'P', long, default_value = "Select: ")]
     prompt: String,
@@ -22,7 +30,15 @@ impl SwitchCommand {
         let target = match self.name.clone() {
             Some(n) => n,
             None => {
-                let picker = FzfPicker::new().with_prompt(&self.prompt);
+                let preview_cmd = if self.preview {
+                    preview_command("session -- {}")
+                } else {
+                    String::new()
+                };
+
+                let picker = FzfPicker::new()
+                    .with_prompt(&self.prompt)
+                    .with_preview_command(&preview_cmd);
                 let sessions = client.list_sessions();
                 match picker.pick(&sessions)? {
                     Some(selection) => selection,
@@ -0,0 +1,100 @@
+use std::path::Path;
+
+use crate::cli::utils::{PREVIEW_CMD, switch_to_pane};
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::history::{PaneHistory, WindowHistory};
+use crate::paths::{self, contract_path};
+use crate::tmux::{Pane, TmuxClient};
+
+/// Switches to a pane via interactive selection.
+///
+/// Lists every pane on the server with its location, command, working directory and
+/// title, most recently focused first, and previews its contents.
+#[derive(clap::Parser, Debug)]
+pub struct SwitchPaneCommand {
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Pane: ")]
+    prompt: String,
+}
+
+impl SwitchPaneCommand {
+    /// Executes the switch pane command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let mut pane_history =
+            PaneHistory::new(paths::pane_history_file_path(), client.server_id());
+        pane_history.load()?;
+        if client.is_inside_tmux() {
+            pane_history.record(&client.current_pane()?.id);
+        }
+
+        let mut panes = client.list_panes();
+        if panes.is_empty() {
+            client.display_message("No panes found")?;
+            return Ok(());
+        }
+        panes.sort_by_key(|pane| pane_history.rank(&pane.id).unwrap_or(usize::MAX));
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(PREVIEW_CMD)
+            .with_delimiter("\t")
+            .with_nth("2..");
+
+        let selection = match picker.pick(&picker_items(&panes))? {
+            Some(sel) => sel,
+            None => return Ok(()), // User canceled
+        };
+
+        let pane_id = selection.split('\t').next().unwrap_or_default();
+        let pane = panes.iter().find(|p| p.id == pane_id).ok_or_else(|| {
+            TsmError::InvalidArgument(format!("Selected pane {} not found", pane_id))
+        })?;
+
+        let mut history = WindowHistory::new(paths::history_file_path());
+        history.load()?;
+        switch_to_pane(client, pane, &mut history, &mut pane_history)
+    }
+}
+
+/// One aligned `pane_id<TAB>session:window.pane  command  cwd  title` line per pane
+fn picker_items(panes: &[Pane]) -> Vec<String> {
+    let locations: Vec<String> = panes
+        .iter()
+        .map(|p| format!("{}:{}.{}", p.session_name, p.window_index, p.index))
+        .collect();
+    let paths: Vec<String> = panes
+        .iter()
+        .map(|p| contract_path(Path::new(&p.path)))
+        .collect();
+
+    let location_width = locations
+        .iter()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let command_width = panes
+        .iter()
+        .map(|p| p.command.chars().count())
+        .max()
+        .unwrap_or(0);
+    let path_width = paths.iter().map(|p| p.chars().count()).max().unwrap_or(0);
+
+    panes
+        .iter()
+        .zip(locations.iter().zip(&paths))
+        .map(|(pane, (location, path))| {
+            format!(
+                "{}\t{:<lw$}  {:<cw$}  {:<pw$}  {}",
+                pane.id,
+                location,
+                pane.command,
+                path,
+                pane.title.replace('\t', " "),
+                lw = location_width,
+                cw = command_width,
+                pw = path_width,
+            )
+        })
+        .collect()
+}
@@ -39,7 +39,7 @@ impl SwitchWindowCommand {
                 (w, last_access)
             })
             .collect();
-        indexed_windows.sort_by(|a, b| b.1.cmp(&a.1));
+        indexed_windows.sort_by_key(|w| std::cmp::Reverse(w.1));
         let windows: Vec<_> = indexed_windows.into_iter().map(|(w, _)| w).collect();
 
         let items = windows
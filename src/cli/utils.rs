@@ -1,12 +1,30 @@
-use crate::error::Result;
-use crate::history::WindowHistory;
-use crate::tmux::{TmuxClient, Window};
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{Result, TsmError};
+use crate::history::{JumpList, PaneHistory, WindowHistory};
+use crate::naming::{NamingStrategy, sanitise_session_name, unique_session_name};
+use crate::paths;
+use crate::tmux::{Pane, TmuxClient, Window};
 
 pub const PREVIEW_CMD: &str = r#"
 PANE_ID=$(echo {} | cut -f1)
 tmux capture-pane -e -p -t "$PANE_ID" 2>/dev/null || echo "No preview available"
 "#;
 
+/// Build an fzf preview command that calls back into tsm, e.g. `preview_command("directory -- {}")`
+pub fn preview_command(args: &str) -> String {
+    let exe = std::env::current_exe()
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| "tsm".to_string());
+
+    format!("'{}' preview {}", exe.replace('\'', "'\\''"), args)
+}
+
 /// Sort windows by access time (most recent first) and return indexed list
 pub fn sort_windows_by_history(
     windows: Vec<Window>,
@@ -21,7 +39,7 @@ pub fn sort_windows_by_history(
             (w, last_access)
         })
         .collect();
-    indexed_windows.sort_by(|a, b| b.1.cmp(&a.1));
+    indexed_windows.sort_by_key(|w| std::cmp::Reverse(w.1));
     indexed_windows
 }
 
@@ -42,3 +60,417 @@ pub fn switch_to_window(
 
     Ok(())
 }
+
+/// Select `pane` and switch to its window, recording both histories
+pub fn switch_to_pane(
+    client: &TmuxClient,
+    pane: &Pane,
+    history: &mut WindowHistory,
+    pane_history: &mut PaneHistory,
+) -> Result<()> {
+    history.record_access(&pane.session_name, pane.window_index);
+    history.save()?;
+    pane_history.record(&pane.id);
+    pane_history.save()?;
+
+    client.select_pane(&pane.id)?;
+
+    if client.is_inside_tmux() {
+        client.switch_to_window(&pane.session_name, pane.window_index)?;
+    } else {
+        client.attach_to_window(&pane.session_name, pane.window_index)?;
+    }
+
+    Ok(())
+}
+
+/// Move through the jumplist towards older (`back`) or newer windows and switch there
+pub fn step_jumplist(client: &TmuxClient, back: bool) -> Result<()> {
+    if !client.is_inside_tmux() {
+        return Err(TsmError::NotInTmux);
+    }
+
+    let current = client.current_window_id()?;
+    let windows = client.list_windows();
+
+    let mut jumps = JumpList::new(paths::jumplist_file_path(), client.server_id());
+    jumps.load()?;
+
+    let target = jumps.step(&current, back, |id| windows.iter().any(|w| w.id == id));
+    jumps.save()?;
+
+    match target.and_then(|id| windows.iter().find(|w| w.id == id)) {
+        Some(window) => {
+            let mut history = WindowHistory::new(paths::history_file_path());
+            history.load()?;
+            switch_to_window(client, window, &mut history)
+        }
+        None if back => client.display_message("Already at the oldest jump"),
+        None => client.display_message("Already at the newest jump"),
+    }
+}
+
+/// Directory only the current user can write to: `$XDG_RUNTIME_DIR` when set, otherwise
+/// a `tsm-<uid>` directory with mode 0700 in the system temp dir.
+///
+/// An existing `tsm-<uid>` is only used if it is a real directory owned by the user and
+/// closed to everyone else.
+fn private_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let uid = Command::new("id")
+        .arg("-u")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .parse::<u32>()
+                .ok()
+        })
+        .ok_or_else(|| {
+            TsmError::InvalidArgument("Failed to find the current user ID".to_string())
+        })?;
+
+    let dir = std::env::temp_dir().join(format!("tsm-{}", uid));
+    match fs::DirBuilder::new().mode(0o700).create(&dir) {
+        Err(err) if err.kind() != io::ErrorKind::AlreadyExists => return Err(err.into()),
+        _ => {}
+    }
+
+    let metadata = fs::symlink_metadata(&dir)?;
+    if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+        return Err(TsmError::Io(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} is not a private directory", dir.display()),
+        )));
+    }
+
+    Ok(dir)
+}
+
+/// Create a fresh file for `edit_in_editor` in `private_dir`.
+///
+/// The file is created exclusively with mode 0600, so an existing file or symlink is
+/// never reused.
+fn create_temp_file(name: &str) -> Result<(PathBuf, fs::File)> {
+    let dir = private_dir()?;
+
+    let mut attempt = 0;
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or(0);
+        let path = dir.join(format!(
+            "tsm-{}-{}-{:08x}",
+            name,
+            std::process::id(),
+            nanos.wrapping_add(attempt)
+        ));
+
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists && attempt < 100 => {
+                attempt += 1;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Open `contents` in `$VISUAL`/`$EDITOR` (falling back to vi) and return the edited text
+pub fn edit_in_editor(name: &str, contents: &str) -> Result<String> {
+    let (file_path, mut file) = create_temp_file(name)?;
+    let written = file.write_all(contents.as_bytes());
+    drop(file);
+    if let Err(err) = written {
+        let _ = fs::remove_file(&file_path);
+        return Err(err.into());
+    }
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    // Run through the shell so editors with arguments (e.g. "code --wait") work
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(&file_path)
+        .status();
+
+    let edited = fs::read_to_string(&file_path);
+    let _ = fs::remove_file(&file_path);
+
+    if !status?.success() {
+        return Err(TsmError::InvalidArgument(format!(
+            "Editor '{}' exited with an error",
+            editor
+        )));
+    }
+
+    Ok(edited?)
+}
+
+/// Ask for a line of input on the terminal, falling back to `default` when left empty.
+///
+/// Returns `None` if input was closed without an answer.
+pub fn prompt_input(prompt: &str, default: &str) -> Result<Option<String>> {
+    if default.is_empty() {
+        print!("{}: ", prompt);
+    } else {
+        print!("{} [{}]: ", prompt, default);
+    }
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Ok(None);
+    }
+
+    let input = input.trim();
+    if input.is_empty() {
+        Ok(Some(default.to_string()))
+    } else {
+        Ok(Some(input.to_string()))
+    }
+}
+
+/// Switch to the session rooted in `path`, or named `name`, creating it if needed.
+///
+/// Without an explicit name, new sessions are named by `strategy` and disambiguated
+/// against existing sessions.
+pub fn open_session(
+    client: &TmuxClient,
+    path: &str,
+    name: Option<&str>,
+    strategy: &NamingStrategy,
+    quiet: bool,
+) -> Result<()> {
+    let sessions = client.list_session_paths();
+    let name = session_name_for(&sessions, path, name, strategy);
+
+    if sessions.iter().any(|(session, _)| *session == name) {
+        if client.is_inside_tmux() {
+            client.switch_session(&name)?;
+        } else {
+            client.attach_session(&name)?;
+        }
+
+        if !quiet {
+            client.display_message(&format!(
+                "{} session already exists. Switching to it.",
+                name
+            ))?;
+        }
+        return Ok(());
+    }
+
+    client.new_session(name.clone(), path.to_string())?;
+
+    if !quiet {
+        client.display_message(&format!("Created new session '{}'", name))?;
+    }
+    Ok(())
+}
+
+/// Every session, most recently attached first, followed by sessions never attached.
+pub fn all_sessions(client: &TmuxClient) -> Vec<String> {
+    let mut sessions = client.list_sessions();
+    for (name, _) in client.list_session_paths() {
+        if !sessions.contains(&name) {
+            sessions.push(name);
+        }
+    }
+    sessions
+}
+
+/// Whether `open_session` would switch to an existing session rather than create one
+pub fn session_exists_for(
+    client: &TmuxClient,
+    path: &str,
+    name: Option<&str>,
+    strategy: &NamingStrategy,
+) -> bool {
+    let sessions = client.list_session_paths();
+    let name = session_name_for(&sessions, path, name, strategy);
+    sessions.iter().any(|(session, _)| *session == name)
+}
+
+/// Name of the session `open_session` uses for `path`: `name`, the session already
+/// rooted there, or a new unique name.
+fn session_name_for(
+    sessions: &[(String, String)],
+    path: &str,
+    name: Option<&str>,
+    strategy: &NamingStrategy,
+) -> String {
+    if let Some(n) = name {
+        sanitise_session_name(n)
+    } else if let Some((existing, _)) = sessions
+        .iter()
+        .find(|(_, session_path)| same_directory(session_path, path))
+    {
+        existing.clone()
+    } else {
+        let taken: Vec<String> = sessions.iter().map(|(name, _)| name.clone()).collect();
+        unique_session_name(Path::new(path), strategy, &taken)
+    }
+}
+
+/// Whether two paths refer to the same directory, ignoring symlinks and trailing slashes
+pub fn same_directory(a: &str, b: &str) -> bool {
+    let canonical = |path: &str| fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+    canonical(a) == canonical(b)
+}
+
+/// Resolve the session to operate on, defaulting to the current one
+pub fn resolve_session(client: &TmuxClient, session: Option<&str>) -> Result<String> {
+    match session {
+        Some(name) => Ok(name.to_string()),
+        None if client.is_inside_tmux() => client.current_session(),
+        None => Err(TsmError::NotInTmux),
+    }
+}
+
+/// List the windows of a single session, ordered by index
+pub fn session_windows(client: &TmuxClient, session: &str) -> Vec<Window> {
+    let mut windows: Vec<_> = client
+        .list_windows()
+        .into_iter()
+        .filter(|w| w.session_name == session)
+        .collect();
+    windows.sort_by_key(|w| w.index);
+    windows
+}
+
+/// Rearrange a session's windows into the given order, reusing the indices they already occupy.
+///
+/// The session's active window stays selected.
+pub fn apply_window_order(client: &TmuxClient, session: &str, order: &[Window]) -> Result<()> {
+    let active_id = client.active_window_id(session)?;
+
+    for (from, to) in window_swaps(order) {
+        client.swap_windows_in_session(session, from, to)?;
+    }
+
+    client.select_window(&active_id)
+}
+
+/// Pairs of window indices to swap, in turn, to put `order` into the indices its windows
+/// already occupy, lowest index first.
+fn window_swaps(order: &[Window]) -> Vec<(u32, u32)> {
+    let mut slots: Vec<u32> = order.iter().map(|w| w.index).collect();
+    slots.sort_unstable();
+
+    let mut arrangement: Vec<&str> = slots
+        .iter()
+        .filter_map(|slot| order.iter().find(|w| w.index == *slot))
+        .map(|w| w.id.as_str())
+        .collect();
+
+    let mut swaps = Vec::new();
+    for (position, window) in order.iter().enumerate() {
+        let Some(current) = arrangement.iter().position(|id| *id == window.id) else {
+            continue;
+        };
+
+        if current != position {
+            swaps.push((slots[current], slots[position]));
+            arrangement.swap(current, position);
+        }
+    }
+
+    swaps
+}
+
+/// Re-key history entries of windows that moved, matching them by window ID
+pub fn migrate_history(client: &TmuxClient, before: &[Window], history: &mut WindowHistory) {
+    let after = client.list_windows();
+
+    let moves: Vec<_> = before
+        .iter()
+        .filter_map(|old| {
+            let new = after.iter().find(|w| w.id == old.id)?;
+            if new.session_name == old.session_name && new.index == old.index {
+                return None;
+            }
+            Some((
+                (old.session_name.clone(), old.index),
+                (new.session_name.clone(), new.index),
+            ))
+        })
+        .collect();
+
+    history.remap(&moves);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn window(id: &str, index: u32) -> Window {
+        Window {
+            id: id.to_string(),
+            session_name: "work".to_string(),
+            index,
+            name: String::new(),
+            pane_id: String::new(),
+        }
+    }
+
+    /// Window IDs by index after applying `window_swaps(order)`
+    fn reordered(order: &[Window]) -> Vec<(u32, String)> {
+        let mut slots: BTreeMap<u32, String> =
+            order.iter().map(|w| (w.index, w.id.clone())).collect();
+
+        for (from, to) in window_swaps(order) {
+            let moved = slots[&from].clone();
+            let replaced = slots.insert(to, moved).unwrap();
+            slots.insert(from, replaced);
+        }
+
+        slots.into_iter().collect()
+    }
+
+    fn slots(ids: &[(u32, &str)]) -> Vec<(u32, String)> {
+        ids.iter().map(|(i, id)| (*i, id.to_string())).collect()
+    }
+
+    #[test]
+    fn windows_take_the_occupied_indices_in_order() {
+        let order = [window("@3", 5), window("@1", 1), window("@2", 2)];
+        assert_eq!(reordered(&order), slots(&[(1, "@3"), (2, "@1"), (5, "@2")]));
+    }
+
+    #[test]
+    fn reversing_windows() {
+        let order = [
+            window("@4", 4),
+            window("@3", 3),
+            window("@2", 2),
+            window("@1", 1),
+        ];
+        assert_eq!(
+            reordered(&order),
+            slots(&[(1, "@4"), (2, "@3"), (3, "@2"), (4, "@1")])
+        );
+    }
+
+    #[test]
+    fn windows_already_in_order_are_not_swapped() {
+        let order = [window("@1", 1), window("@2", 3), window("@3", 7)];
+        assert!(window_swaps(&order).is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+use crate::cli::utils::{open_session, preview_command, prompt_input};
+use crate::config::Config;
+use crate::error::{Result, TsmError};
+use crate::fzf::FzfPicker;
+use crate::git::Repository;
+use crate::naming::NamingStrategy;
+use crate::paths::expand_path;
+use crate::tmux::TmuxClient;
+
+/// Entry in the picker that creates a worktree for a new branch
+const NEW_BRANCH_ITEM: &str = "＋ new branch";
+
+/// Opens a git worktree as its own session.
+///
+/// Lists the worktrees of the current repository plus branches without one.
+/// Picking a branch creates a worktree for it. The session is named `repo@branch`
+/// and rooted in the worktree; an existing session for the worktree is reused.
+#[derive(clap::Parser, Debug)]
+pub struct WorktreeCommand {
+    /// Branch to open, created along with its worktree if it does not exist
+    #[clap(short, long)]
+    branch: Option<String>,
+
+    /// Repository path (defaults to the current pane's directory)
+    #[clap(short, long)]
+    path: Option<String>,
+
+    /// Show directory preview in fzf
+    #[clap(short = 'v', long, default_value_t = false)]
+    preview: bool,
+
+    /// fzf prompt
+    #[clap(short = 'P', long, default_value = "Select worktree: ")]
+    prompt: String,
+
+    /// No success message
+    #[clap(short = 'q', long, default_value_t = false)]
+    quiet: bool,
+}
+
+impl WorktreeCommand {
+    /// Executes the worktree command.
+    pub fn run(&self, client: &TmuxClient) -> Result<()> {
+        let start = match &self.path {
+            Some(path) => PathBuf::from(expand_path(path)),
+            None => current_directory(client)?,
+        };
+
+        let repo = Repository::discover(&start).ok_or_else(|| {
+            TsmError::InvalidArgument(format!(
+                "{} is not inside a git repository",
+                start.display()
+            ))
+        })?;
+
+        let branch = match &self.branch {
+            Some(branch) => branch.clone(),
+            None => match self.pick(&repo)? {
+                Some(Selection::Worktree(path)) => {
+                    return self.open(client, &path);
+                }
+                Some(Selection::Branch(branch)) => branch,
+                None => return Ok(()),
+            },
+        };
+
+        let worktrees = repo.worktrees()?;
+        let path = match worktrees
+            .iter()
+            .find(|w| w.branch.as_deref() == Some(branch.as_str()))
+        {
+            Some(worktree) => worktree.path.clone(),
+            None => {
+                let path = worktree_path(&repo, &branch, &Config::load()?.worktree.path);
+                repo.add_worktree(&path, &branch)?;
+                path
+            }
+        };
+
+        self.open(client, &path)
+    }
+
+    fn open(&self, client: &TmuxClient, path: &Path) -> Result<()> {
+        open_session(
+            client,
+            &path.to_string_lossy(),
+            None,
+            &NamingStrategy::RepoBranch,
+            self.quiet,
+        )
+    }
+
+    /// Prompts for a worktree, a branch without one, or a new branch.
+    fn pick(&self, repo: &Repository) -> Result<Option<Selection>> {
+        let worktrees = repo.worktrees()?;
+
+        let mut items: Vec<String> = worktrees
+            .iter()
+            .map(|w| {
+                format!(
+                    "worktree\t{}\t{}  {}",
+                    w.path.display(),
+                    w.branch.as_deref().unwrap_or("(detached)"),
+                    w.path.display()
+                )
+            })
+            .collect();
+
+        for branch in repo.branches()? {
+            if !worktrees.iter().any(|w| w.branch.as_ref() == Some(&branch)) {
+                items.push(format!("branch\t{}\t＋ {}", branch, branch));
+            }
+        }
+        items.push(format!("new\t\t{}", NEW_BRANCH_ITEM));
+
+        let preview_cmd = if self.preview {
+            preview_command("directory -- {2}")
+        } else {
+            String::new()
+        };
+
+        let picker = FzfPicker::new()
+            .with_prompt(&self.prompt)
+            .with_preview_command(&preview_cmd)
+            .with_delimiter("\t")
+            .with_nth("3..");
+
+        let Some(selection) = picker.pick(&items)? else {
+            return Ok(None);
+        };
+
+        let mut fields = selection.split('\t');
+
+        match (fields.next(), fields.next()) {
+            (Some("worktree"), Some(path)) => Ok(Some(Selection::Worktree(PathBuf::from(path)))),
+            (Some("branch"), Some(branch)) => Ok(Some(Selection::Branch(branch.to_string()))),
+            (Some("new"), _) => Ok(prompt_input("New branch name", "")?
+                .filter(|name| !name.is_empty())
+                .map(Selection::Branch)),
+            _ => Ok(None),
+        }
+    }
+}
+
+enum Selection {
+    Worktree(PathBuf),
+    Branch(String),
+}
+
+/// Directory of the current pane, or the working directory outside tmux
+fn current_directory(client: &TmuxClient) -> Result<PathBuf> {
+    if client.is_inside_tmux() {
+        let (session, window_index) = client.get_current_window()?;
+        return Ok(PathBuf::from(
+            client.get_pane_current_path(&session, window_index)?,
+        ));
+    }
+
+    Ok(std::env::current_dir()?)
+}
+
+/// Location for a new worktree, from the configured template
+fn worktree_path(repo: &Repository, branch: &str, template: &str) -> PathBuf {
+    let parent = repo
+        .main_root()
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    PathBuf::from(expand_path(
+        &template
+            .replace("{parent}", &parent)
+            .replace("{repo}", &repo.name())
+            .replace("{branch}", &branch.replace('/', "-")),
+    ))
+}
@@ -0,0 +1,210 @@
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::directories::SourceKind;
+use crate::error::{Result, TsmError};
+use crate::naming::NamingStrategy;
+use crate::paths;
+
+/// User configuration, read from `config.toml`.
+///
+/// Every field is optional; a missing file yields the defaults.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Settings for sessions created by `tsm new`
+    pub session: SessionConfig,
+
+    /// Settings for `tsm worktree`
+    pub worktree: WorktreeConfig,
+
+    /// Settings for `tsm new --clone`
+    pub clone: CloneConfig,
+
+    /// Where `tsm new` finds directories to offer
+    pub directories: DirectoriesConfig,
+
+    /// Monorepos whose packages are offered by `tsm new`
+    pub workspaces: WorkspacesConfig,
+
+    /// Settings for picker previews
+    pub preview: PreviewConfig,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionConfig {
+    /// How session names are derived from directories
+    pub naming: NamingStrategy,
+
+    /// Whether to use the git repository root when a subdirectory is selected
+    pub repo_root: RepoRoot,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorktreeConfig {
+    /// Where new worktrees are created; `{parent}` is the directory containing the
+    /// main working tree, `{repo}` the repository name and `{branch}` the branch
+    pub path: String,
+}
+
+impl Default for WorktreeConfig {
+    fn default() -> Self {
+        Self {
+            path: "{parent}/{repo}@{branch}".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct CloneConfig {
+    /// Where repositories are cloned, using `{host}`, `{owner}` and `{repo}` from the URL
+    pub path: String,
+}
+
+impl Default for CloneConfig {
+    fn default() -> Self {
+        Self {
+            path: "~/code/{host}/{owner}/{repo}".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct DirectoriesConfig {
+    /// Sources to query, in the order their directories are listed
+    pub sources: Vec<SourceKind>,
+
+    /// Globs matched against full paths; matching directories are never listed
+    pub ignore: Vec<String>,
+
+    /// List directories that already have a session before the others
+    pub sessions_first: bool,
+
+    /// Directories that are always offered
+    pub bookmarks: Vec<String>,
+
+    /// Shell command printing one directory per line
+    pub command: Option<String>,
+
+    /// Directories scanned for git repositories
+    pub git: GitScanConfig,
+}
+
+impl Default for DirectoriesConfig {
+    fn default() -> Self {
+        Self {
+            sources: vec![
+                SourceKind::Zoxide,
+                SourceKind::Bookmarks,
+                SourceKind::Workspaces,
+                SourceKind::Git,
+                SourceKind::Command,
+            ],
+            ignore: vec![],
+            sessions_first: false,
+            bookmarks: vec![],
+            command: None,
+            git: GitScanConfig::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitScanConfig {
+    /// Directories searched for repositories
+    pub roots: Vec<String>,
+
+    /// How many levels below each root are searched
+    pub depth: usize,
+}
+
+impl Default for GitScanConfig {
+    fn default() -> Self {
+        Self {
+            roots: vec![],
+            depth: 2,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkspacesConfig {
+    /// Directories holding a Cargo, npm/yarn or pnpm workspace, or containing such
+    /// directories one level down
+    pub roots: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct PreviewConfig {
+    /// How directory contents are shown
+    pub listing: Listing,
+
+    /// Levels shown by the tree listing
+    pub tree_depth: usize,
+
+    /// Lines of the README shown; 0 hides it
+    pub readme_lines: usize,
+
+    /// Render listings with `eza` when it is installed
+    pub eza: bool,
+
+    /// Render the README with `bat` when it is installed
+    pub bat: bool,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            listing: Listing::Ls,
+            tree_depth: 2,
+            readme_lines: 15,
+            eza: true,
+            bat: true,
+        }
+    }
+}
+
+/// Layout of the directory listing in previews
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Listing {
+    /// Directory contents, directories first
+    Ls,
+    /// Nested contents down to `tree_depth`
+    Tree,
+}
+
+/// Choice between a selected subdirectory and its git repository root
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RepoRoot {
+    /// Ask when the directory was picked interactively
+    #[default]
+    Ask,
+    /// Always use the repository root
+    Always,
+    /// Always use the selected directory
+    Never,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let path = paths::config_file_path();
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        toml::from_str(&contents)
+            .map_err(|e| TsmError::Config(format!("{}: {}", path.display(), e)))
+    }
+}
@@ -0,0 +1,224 @@
+pub mod sources;
+
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::paths::expand_path;
+
+pub use sources::{BookmarkSource, CommandSource, GitScanSource, WorkspaceSource, ZoxideSource};
+
+/// A directory offered by a source, with labels shown next to it
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub path: PathBuf,
+    pub labels: Vec<String>,
+}
+
+impl Entry {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            labels: vec![],
+        }
+    }
+
+    pub fn with_label(mut self, label: String) -> Self {
+        self.labels.push(label);
+        self
+    }
+}
+
+/// Provider of candidate directories for new sessions
+pub trait DirectorySource {
+    fn directories(&self) -> Result<Vec<Entry>>;
+}
+
+/// Built-in directory sources, as named in the config file
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceKind {
+    /// Directories ranked by zoxide
+    Zoxide,
+    /// The configured bookmark list
+    Bookmarks,
+    /// Packages of the configured workspaces
+    Workspaces,
+    /// Git repositories below the configured roots
+    Git,
+    /// Output of the configured command
+    Command,
+}
+
+/// Builds the configured sources in their configured order.
+pub fn configured_sources(config: &Config) -> Vec<Box<dyn DirectorySource>> {
+    let dirs = &config.directories;
+    let expand_all = |paths: &[String]| -> Vec<PathBuf> {
+        paths
+            .iter()
+            .map(|path| PathBuf::from(expand_path(path)))
+            .collect()
+    };
+
+    dirs.sources
+        .iter()
+        .filter_map(|kind| -> Option<Box<dyn DirectorySource>> {
+            match kind {
+                SourceKind::Zoxide => Some(Box::new(ZoxideSource::default())),
+                SourceKind::Bookmarks => Some(Box::new(BookmarkSource {
+                    paths: expand_all(&dirs.bookmarks),
+                })),
+                SourceKind::Workspaces => Some(Box::new(WorkspaceSource {
+                    roots: expand_all(&config.workspaces.roots),
+                })),
+                SourceKind::Git => Some(Box::new(GitScanSource {
+                    roots: expand_all(&dirs.git.roots),
+                    depth: dirs.git.depth,
+                })),
+                SourceKind::Command => dirs.command.as_ref().map(|command| {
+                    Box::new(CommandSource {
+                        command: command.clone(),
+                    }) as Box<dyn DirectorySource>
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Queries every source and merges their directories.
+///
+/// Directories keep the position of their first occurrence and collect the labels of
+/// every source listing them. A failing source is skipped; its error is only returned when
+/// no source produced anything.
+pub fn collect(sources: &[Box<dyn DirectorySource>], ignore: &[String]) -> Result<Vec<Entry>> {
+    let ignore: Vec<Pattern> = ignore
+        .iter()
+        .filter_map(|glob| Pattern::new(&expand_path(glob)).ok())
+        .collect();
+
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut first_error = None;
+
+    for source in sources {
+        let found = match source.directories() {
+            Ok(found) => found,
+            Err(e) => {
+                first_error.get_or_insert(e);
+                continue;
+            }
+        };
+
+        for entry in found {
+            if ignore.iter().any(|p| p.matches_path(&entry.path)) {
+                continue;
+            }
+
+            match entries.iter_mut().find(|e| same_path(&e.path, &entry.path)) {
+                Some(existing) => {
+                    for label in entry.labels {
+                        if !existing.labels.contains(&label) {
+                            existing.labels.push(label);
+                        }
+                    }
+                }
+                None => entries.push(entry),
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) if entries.is_empty() => Err(e),
+        _ => Ok(entries),
+    }
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    a.components().eq(b.components())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TsmError;
+
+    /// Source listing `(path, label)` pairs, or failing when `None`
+    struct Fixed(Option<Vec<(&'static str, &'static str)>>);
+
+    impl DirectorySource for Fixed {
+        fn directories(&self) -> Result<Vec<Entry>> {
+            let entries = self
+                .0
+                .as_ref()
+                .ok_or_else(|| TsmError::InvalidArgument("source failed".to_string()))?;
+
+            Ok(entries
+                .iter()
+                .map(|(path, label)| Entry::new(PathBuf::from(path)).with_label(label.to_string()))
+                .collect())
+        }
+    }
+
+    fn sources(sources: Vec<Fixed>) -> Vec<Box<dyn DirectorySource>> {
+        sources
+            .into_iter()
+            .map(|s| Box::new(s) as Box<dyn DirectorySource>)
+            .collect()
+    }
+
+    fn summary(entries: &[Entry]) -> Vec<(String, Vec<String>)> {
+        entries
+            .iter()
+            .map(|e| (e.path.to_string_lossy().into_owned(), e.labels.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn duplicates_keep_their_first_position_and_merge_labels() {
+        let sources = sources(vec![
+            Fixed(Some(vec![("/src/api", "zoxide"), ("/src/web", "zoxide")])),
+            Fixed(Some(vec![("/src/cli", "git"), ("/src/api/", "git")])),
+            Fixed(Some(vec![("/src/api", "git")])),
+        ]);
+
+        assert_eq!(
+            summary(&collect(&sources, &[]).unwrap()),
+            vec![
+                (
+                    "/src/api".to_string(),
+                    vec!["zoxide".to_string(), "git".to_string()]
+                ),
+                ("/src/web".to_string(), vec!["zoxide".to_string()]),
+                ("/src/cli".to_string(), vec!["git".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignored_directories_are_dropped() {
+        let sources = sources(vec![Fixed(Some(vec![
+            ("/src/api", "git"),
+            ("/src/api/node_modules/left-pad", "git"),
+        ]))]);
+
+        let entries = collect(&sources, &["**/node_modules/*".to_string()]).unwrap();
+        assert_eq!(
+            summary(&entries),
+            vec![("/src/api".to_string(), vec!["git".to_string()])]
+        );
+    }
+
+    #[test]
+    fn failing_sources_are_skipped() {
+        let sources = sources(vec![Fixed(None), Fixed(Some(vec![("/src/api", "git")]))]);
+        assert_eq!(collect(&sources, &[]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn errors_surface_when_nothing_was_found() {
+        let sources = sources(vec![Fixed(None), Fixed(Some(vec![]))]);
+        assert!(collect(&sources, &[]).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::directories::{DirectorySource, Entry};
+use crate::error::{Result, TsmError};
+use crate::paths::expand_path;
+use crate::{workspace, zoxide};
+
+/// Directories ranked by zoxide, best first, labelled with their score
+#[derive(Default)]
+pub struct ZoxideSource {
+    /// Keywords passed to `zoxide query`; all directories when empty
+    pub keywords: Vec<String>,
+}
+
+impl DirectorySource for ZoxideSource {
+    fn directories(&self) -> Result<Vec<Entry>> {
+        Ok(zoxide::query_directories(&self.keywords)?
+            .into_iter()
+            .map(|dir| Entry::new(dir.path).with_label(format!("{:.1}", dir.score)))
+            .collect())
+    }
+}
+
+/// A fixed list of directories
+pub struct BookmarkSource {
+    pub paths: Vec<PathBuf>,
+}
+
+impl DirectorySource for BookmarkSource {
+    fn directories(&self) -> Result<Vec<Entry>> {
+        Ok(self
+            .paths
+            .iter()
+            .filter(|path| path.is_dir())
+            .map(|path| Entry::new(path.clone()))
+            .collect())
+    }
+}
+
+/// Member packages of Cargo, npm and pnpm workspaces, labelled with their workspace
+pub struct WorkspaceSource {
+    pub roots: Vec<PathBuf>,
+}
+
+impl DirectorySource for WorkspaceSource {
+    fn directories(&self) -> Result<Vec<Entry>> {
+        Ok(workspace::discover_packages(&self.roots)
+            .into_iter()
+            .map(|package| {
+                Entry::new(package.path)
+                    .with_label(format!("{} · {}", package.workspace, package.kind))
+            })
+            .collect())
+    }
+}
+
+/// Git repositories found below a set of roots
+pub struct GitScanSource {
+    pub roots: Vec<PathBuf>,
+    /// Levels below each root that are searched; the root itself is level 0
+    pub depth: usize,
+}
+
+impl DirectorySource for GitScanSource {
+    fn directories(&self) -> Result<Vec<Entry>> {
+        let mut repos = Vec::new();
+        for root in &self.roots {
+            find_repositories(root, self.depth, &mut repos);
+        }
+
+        Ok(repos.into_iter().map(Entry::new).collect())
+    }
+}
+
+/// Collects repositories at or below `dir` without descending into them.
+fn find_repositories(dir: &Path, depth: usize, repos: &mut Vec<PathBuf>) {
+    if dir.join(".git").exists() {
+        repos.push(dir.to_path_buf());
+        return;
+    }
+
+    if depth == 0 {
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut children: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    children.sort();
+
+    for child in children {
+        find_repositories(&child, depth - 1, repos);
+    }
+}
+
+/// Directories printed one per line by a shell command
+pub struct CommandSource {
+    pub command: String,
+}
+
+impl DirectorySource for CommandSource {
+    fn directories(&self) -> Result<Vec<Entry>> {
+        let output = Command::new("sh").arg("-c").arg(&self.command).output()?;
+
+        if !output.status.success() {
+            return Err(TsmError::SourceCommand(format!(
+                "{}: {}",
+                self.command,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| Entry::new(PathBuf::from(expand_path(line))))
+            .collect())
+    }
+}
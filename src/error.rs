@@ -15,11 +15,23 @@ pub enum TsmError {
     #[error("failed to execute fzf command: {0}")]
     Fzf(String),
 
+    #[error("git command failed: {0}")]
+    Git(String),
+
     #[error("zoxide is not installed or failed to execute")]
     ZoxideQueryFailed,
 
+    #[error("directory source command failed: {0}")]
+    SourceCommand(String),
+
+    #[error("failed to kill process: {0}")]
+    Kill(String),
+
     #[error("{0}")]
     InvalidArgument(String),
+
+    #[error("invalid config file {0}")]
+    Config(String),
 }
 
 pub type Result<T> = std::result::Result<T, TsmError>;
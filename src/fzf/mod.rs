@@ -9,6 +9,7 @@ pub struct FzfPicker {
     preview_window: String,
     delimiter: Option<String>,
     with_nth: Option<String>,
+    multi: bool,
 }
 
 impl FzfPicker {
@@ -19,6 +20,7 @@ impl FzfPicker {
             preview_window: "right:60%".to_string(),
             delimiter: None,
             with_nth: None,
+            multi: false,
         }
     }
 
@@ -42,10 +44,39 @@ impl FzfPicker {
         self
     }
 
+    pub fn with_multi(mut self) -> Self {
+        self.multi = true;
+        self
+    }
+
     pub fn pick(&self, items: &[String]) -> Result<Option<String>> {
+        Ok(self
+            .run(items)?
+            .map(|selection| selection.trim().to_string()))
+    }
+
+    /// Picks any number of items; requires `with_multi` to select more than one.
+    pub fn pick_multi(&self, items: &[String]) -> Result<Vec<String>> {
+        Ok(self
+            .run(items)?
+            .map(|selection| {
+                selection
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(|line| line.to_string())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn run(&self, items: &[String]) -> Result<Option<String>> {
         let mut fzf = Command::new("fzf");
         fzf.arg("--ansi").arg(format!("--prompt={}", self.prompt));
 
+        if self.multi {
+            fzf.arg("--multi");
+        }
+
         if let Some(delimiter) = &self.delimiter {
             fzf.arg("--delimiter").arg(delimiter);
         }
@@ -76,8 +107,7 @@ impl FzfPicker {
         let output = child.wait_with_output()?;
 
         if output.status.success() {
-            let selection = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            Ok(Some(selection))
+            Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
         } else {
             Ok(None)
         }
@@ -0,0 +1,116 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::Result;
+
+/// A saved window: where it lived, where it ran and what it ran
+#[derive(Clone)]
+pub struct Bookmark {
+    pub name: String,
+    pub description: String,
+    pub session: String,
+    /// Window ID (`@N`) the bookmark was saved from, or last recreated as
+    pub window_id: String,
+    pub window_name: String,
+    pub path: String,
+    /// Foreground command of the pane; empty if it was an idle shell
+    pub command: String,
+    /// `TmuxClient::server_id` of the server `window_id` belongs to
+    pub server: String,
+}
+
+/// Named window bookmarks.
+///
+/// The file holds one tab-separated line per bookmark:
+/// `name description session window_id window_name path command server`.
+/// Files written before the server column was added load with an empty server.
+pub struct Bookmarks {
+    file_path: PathBuf,
+    entries: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn load(&mut self) -> Result<()> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        for line in fs::read_to_string(&self.file_path)?.lines() {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() == 7 || parts.len() == 8 {
+                self.entries.push(Bookmark {
+                    name: parts[0].to_string(),
+                    description: parts[1].to_string(),
+                    session: parts[2].to_string(),
+                    window_id: parts[3].to_string(),
+                    window_name: parts[4].to_string(),
+                    path: parts[5].to_string(),
+                    command: parts[6].to_string(),
+                    server: parts.get(7).unwrap_or(&"").to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut file = File::create(&self.file_path)?;
+        for b in &self.entries {
+            writeln!(
+                file,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                b.name,
+                b.description,
+                b.session,
+                b.window_id,
+                b.window_name,
+                b.path,
+                b.command,
+                b.server
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Adds a bookmark, replacing any bookmark with the same name.
+    pub fn add(&mut self, bookmark: Bookmark) {
+        let clean = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+        let bookmark = Bookmark {
+            name: clean(&bookmark.name),
+            description: clean(&bookmark.description),
+            session: clean(&bookmark.session),
+            window_id: clean(&bookmark.window_id),
+            window_name: clean(&bookmark.window_name),
+            path: clean(&bookmark.path),
+            command: clean(&bookmark.command),
+            server: clean(&bookmark.server),
+        };
+
+        self.entries.retain(|b| b.name != bookmark.name);
+        self.entries.push(bookmark);
+    }
+
+    /// Removes the bookmark called `name`, returning whether it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|b| b.name != name);
+        self.entries.len() != before
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Bookmark> {
+        self.entries.iter_mut().find(|b| b.name == name)
+    }
+
+    pub fn entries(&self) -> &[Bookmark] {
+        &self.entries
+    }
+}
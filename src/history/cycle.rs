@@ -0,0 +1,214 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::Result;
+
+/// What a cycle walks through
+#[derive(Clone, PartialEq, Eq)]
+pub enum CycleScope {
+    /// Windows of every session
+    Windows,
+    /// Sessions, each represented by its most recently used window
+    Sessions,
+    /// Windows of the named session
+    Session(String),
+}
+
+impl CycleScope {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "windows" => Some(Self::Windows),
+            "sessions" => Some(Self::Sessions),
+            _ => value
+                .strip_prefix("current-session:")
+                .map(|name| Self::Session(name.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for CycleScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Windows => write!(f, "windows"),
+            Self::Sessions => write!(f, "sessions"),
+            Self::Session(name) => write!(f, "current-session:{}", name),
+        }
+    }
+}
+
+/// An Alt-Tab style walk through the MRU order that has not been committed yet.
+///
+/// The order is frozen when the walk starts, so the windows passed through are not
+/// recorded as accessed. The file holds `scope position last_press timeout` on its
+/// first line, followed by one window ID per line in MRU order.
+pub struct Cycle {
+    file_path: PathBuf,
+    pub scope: CycleScope,
+    pub position: usize,
+    /// Time of the latest press, in milliseconds since the epoch
+    pub last_press: u64,
+    /// Milliseconds without a press after which the walk is committed
+    pub timeout: u64,
+    pub items: Vec<String>,
+}
+
+impl Cycle {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            scope: CycleScope::Windows,
+            position: 0,
+            last_press: 0,
+            timeout: 0,
+            items: Vec::new(),
+        }
+    }
+
+    /// Loads the walk in progress, returning false if there is none.
+    pub fn load(&mut self) -> Result<bool> {
+        if !self.file_path.exists() {
+            return Ok(false);
+        }
+
+        let contents = fs::read_to_string(&self.file_path)?;
+        let mut lines = contents.lines();
+
+        let header: Vec<&str> = lines.next().unwrap_or_default().split('\t').collect();
+        if header.len() != 4 {
+            return Ok(false);
+        }
+
+        let (Some(scope), Ok(position), Ok(last_press), Ok(timeout)) = (
+            CycleScope::parse(header[0]),
+            header[1].parse(),
+            header[2].parse(),
+            header[3].parse(),
+        ) else {
+            return Ok(false);
+        };
+
+        self.scope = scope;
+        self.position = position;
+        self.last_press = last_press;
+        self.timeout = timeout;
+        self.items = lines.map(str::to_string).collect();
+
+        Ok(!self.items.is_empty())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut file = File::create(&self.file_path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            self.scope, self.position, self.last_press, self.timeout
+        )?;
+        for item in &self.items {
+            writeln!(file, "{}", item)?;
+        }
+        Ok(())
+    }
+
+    /// Ends the walk.
+    pub fn clear(&self) {
+        let _ = fs::remove_file(&self.file_path);
+    }
+
+    /// Moves one item towards less recently used items, or back towards the most
+    /// recently used one, wrapping around at either end.
+    pub fn step(&mut self, back: bool) {
+        let len = self.items.len();
+        if len == 0 {
+            return;
+        }
+
+        self.position = if back {
+            (self.position.min(len - 1) + len - 1) % len
+        } else {
+            (self.position + 1) % len
+        };
+    }
+
+    /// Whether the latest press was recent enough to continue the walk.
+    pub fn is_active(&self) -> bool {
+        now_millis().saturating_sub(self.last_press) <= self.timeout
+    }
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(items: &[&str], position: usize) -> Cycle {
+        let mut cycle = Cycle::new(PathBuf::new());
+        cycle.items = items.iter().map(|i| i.to_string()).collect();
+        cycle.position = position;
+        cycle
+    }
+
+    #[test]
+    fn steps_wrap_around() {
+        let mut walk = cycle(&["@1", "@2", "@3"], 0);
+
+        walk.step(false);
+        assert_eq!(walk.position, 1);
+        walk.step(false);
+        walk.step(false);
+        assert_eq!(walk.position, 0);
+        walk.step(true);
+        assert_eq!(walk.position, 2);
+    }
+
+    #[test]
+    fn stepping_back_from_past_the_end() {
+        // Windows closed during the walk can leave the position past the last item
+        let mut walk = cycle(&["@1", "@2"], 4);
+        walk.step(true);
+        assert_eq!(walk.position, 0);
+    }
+
+    #[test]
+    fn scopes_round_trip() {
+        for scope in [
+            CycleScope::Windows,
+            CycleScope::Sessions,
+            CycleScope::Session("api".to_string()),
+            CycleScope::Session(String::new()),
+        ] {
+            assert!(CycleScope::parse(&scope.to_string()) == Some(scope));
+        }
+        assert!(CycleScope::parse("panes").is_none());
+    }
+
+    #[test]
+    fn saved_walks_load_back() {
+        let file_path = std::env::temp_dir().join(format!("tsm-cycle-test-{}", std::process::id()));
+
+        let mut walk = cycle(&["@4", "@2"], 1);
+        walk.file_path = file_path.clone();
+        walk.scope = CycleScope::Session("api".to_string());
+        walk.last_press = 1234;
+        walk.timeout = 500;
+        walk.save().unwrap();
+
+        let mut loaded = Cycle::new(file_path);
+        assert!(loaded.load().unwrap());
+        loaded.clear();
+
+        assert!(loaded.scope == walk.scope);
+        assert_eq!(loaded.position, 1);
+        assert_eq!(loaded.last_press, 1234);
+        assert_eq!(loaded.timeout, 500);
+        assert_eq!(loaded.items, walk.items);
+    }
+}
@@ -0,0 +1,133 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::Result;
+
+/// Maximum number of visits kept
+const MAX_ENTRIES: usize = 100;
+
+/// Chronological list of visited windows with a cursor, like vim's jumplist.
+///
+/// Windows are stored by their tmux window ID (`@N`), which stays the same when a
+/// window is moved or renumbered but is handed out again after a server restart. The
+/// file holds the `server` the IDs belong to and the cursor on its first two lines,
+/// followed by one window ID per line, oldest first. A list saved on another server is
+/// discarded on load.
+pub struct JumpList {
+    file_path: PathBuf,
+    server: String,
+    entries: Vec<String>,
+    position: usize,
+}
+
+impl JumpList {
+    /// Jump list for the tmux server identified by `server` (see `TmuxClient::server_id`).
+    pub fn new(file_path: PathBuf, server: String) -> Self {
+        Self {
+            file_path,
+            server,
+            entries: Vec::new(),
+            position: 0,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<()> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.file_path)?;
+        let mut lines = contents.lines();
+        if lines.next() != Some(self.server.as_str()) {
+            return Ok(());
+        }
+
+        let position = lines.next().and_then(|l| l.parse().ok()).unwrap_or(0);
+        self.entries = lines
+            .filter(|l| l.starts_with('@'))
+            .map(str::to_string)
+            .collect();
+        self.position = position.min(self.entries.len().saturating_sub(1));
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut file = File::create(&self.file_path)?;
+        writeln!(file, "{}", self.server)?;
+        writeln!(file, "{}", self.position)?;
+        for window_id in &self.entries {
+            writeln!(file, "{}", window_id)?;
+        }
+        Ok(())
+    }
+
+    /// Records a visit to `window_id`.
+    ///
+    /// Visiting the window under the cursor is a no-op, so switches made by `back` and
+    /// `forward` don't record themselves. Any other visit drops the entries after the
+    /// cursor and becomes the newest entry.
+    pub fn record(&mut self, window_id: &str) {
+        if self.current() == Some(window_id) {
+            return;
+        }
+
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.position + 1);
+        }
+        self.entries.push(window_id.to_string());
+
+        if self.entries.len() > MAX_ENTRIES {
+            let excess = self.entries.len() - MAX_ENTRIES;
+            self.entries.drain(..excess);
+        }
+
+        self.position = self.entries.len() - 1;
+    }
+
+    /// Moves the cursor towards older (`back`) or newer entries, starting from `current`.
+    ///
+    /// Skips windows that no longer exist according to `exists` and entries for the
+    /// current window itself. Returns the window ID to switch to.
+    pub fn step(
+        &mut self,
+        current: &str,
+        back: bool,
+        exists: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        self.record(current);
+
+        let candidates: Vec<usize> = if back {
+            (0..self.position).rev().collect()
+        } else {
+            (self.position + 1..self.entries.len()).collect()
+        };
+
+        let target = candidates
+            .into_iter()
+            .find(|&i| self.entries[i] != current && exists(&self.entries[i]))?;
+
+        self.position = target;
+        Some(self.entries[target].clone())
+    }
+
+    /// Window ID under the cursor
+    pub fn current(&self) -> Option<&str> {
+        self.entries.get(self.position).map(String::as_str)
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: usize) {
+        if position < self.entries.len() {
+            self.position = position;
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::Result;
+
+/// Numbered window marks, harpoon style.
+///
+/// Windows are stored by their tmux window ID (`@N`), so a mark keeps pointing at the
+/// same window when it is moved, swapped or renumbered. tmux numbers windows from `@0`
+/// again after a restart, so the file starts with the `server` the IDs belong to and
+/// marks saved on another server are dropped. The rest of the file holds one
+/// `slot<TAB>window_id` line per mark.
+pub struct Marks {
+    file_path: PathBuf,
+    server: String,
+    slots: BTreeMap<u32, String>,
+}
+
+impl Marks {
+    /// Marks for the tmux server identified by `server` (see `TmuxClient::server_id`).
+    pub fn new(file_path: PathBuf, server: String) -> Self {
+        Self {
+            file_path,
+            server,
+            slots: BTreeMap::new(),
+        }
+    }
+
+    pub fn load(&mut self) -> Result<()> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.file_path)?;
+        let mut lines = contents.lines();
+        if lines.next() != Some(self.server.as_str()) {
+            return Ok(());
+        }
+
+        for line in lines {
+            if let Some((slot, window_id)) = line.split_once('\t')
+                && let Ok(slot) = slot.parse::<u32>()
+            {
+                self.slots.insert(slot, window_id.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut file = File::create(&self.file_path)?;
+        writeln!(file, "{}", self.server)?;
+        for (slot, window_id) in &self.slots {
+            writeln!(file, "{}\t{}", slot, window_id)?;
+        }
+        Ok(())
+    }
+
+    /// Pins `window_id` to `slot`, removing it from any other slot.
+    pub fn set(&mut self, slot: u32, window_id: &str) {
+        self.slots.retain(|_, id| id != window_id);
+        self.slots.insert(slot, window_id.to_string());
+    }
+
+    pub fn get(&self, slot: u32) -> Option<&str> {
+        self.slots.get(&slot).map(String::as_str)
+    }
+
+    /// Marks in slot order
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.slots.iter().map(|(slot, id)| (*slot, id.as_str()))
+    }
+
+    /// Replaces every mark.
+    pub fn replace(&mut self, slots: BTreeMap<u32, String>) {
+        self.slots = slots;
+    }
+}
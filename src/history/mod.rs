@@ -1,3 +1,13 @@
+pub mod bookmarks;
+pub mod cycle;
+pub mod jumplist;
+pub mod marks;
+pub mod panes;
 pub mod tracker;
 
+pub use bookmarks::{Bookmark, Bookmarks};
+pub use cycle::{Cycle, CycleScope};
+pub use jumplist::JumpList;
+pub use marks::Marks;
+pub use panes::PaneHistory;
 pub use tracker::WindowHistory;
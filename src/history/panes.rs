@@ -0,0 +1,80 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::Result;
+
+/// Maximum number of panes kept
+const MAX_ENTRIES: usize = 100;
+
+/// Focused panes, most recent first.
+///
+/// Panes are stored by their tmux pane ID (`%N`), which survives moving the pane or
+/// its window but is only unique while the server runs: a restarted server numbers
+/// panes from `%0` again. The file holds the `server` the IDs belong to on its first
+/// line, followed by one pane ID per line; a history saved on another server is
+/// discarded on load.
+pub struct PaneHistory {
+    file_path: PathBuf,
+    server: String,
+    entries: Vec<String>,
+}
+
+impl PaneHistory {
+    /// Pane history for the tmux server identified by `server` (see `TmuxClient::server_id`).
+    pub fn new(file_path: PathBuf, server: String) -> Self {
+        Self {
+            file_path,
+            server,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn load(&mut self) -> Result<()> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.file_path)?;
+        let mut lines = contents.lines();
+        if lines.next() != Some(self.server.as_str()) {
+            return Ok(());
+        }
+
+        self.entries = lines
+            .filter(|l| l.starts_with('%'))
+            .map(str::to_string)
+            .collect();
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut file = File::create(&self.file_path)?;
+        writeln!(file, "{}", self.server)?;
+        for pane_id in &self.entries {
+            writeln!(file, "{}", pane_id)?;
+        }
+        Ok(())
+    }
+
+    /// Moves `pane_id` to the front of the history.
+    pub fn record(&mut self, pane_id: &str) {
+        self.entries.retain(|id| id != pane_id);
+        self.entries.insert(0, pane_id.to_string());
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    /// Position of `pane_id` in the history, 0 being the most recent.
+    pub fn rank(&self, pane_id: &str) -> Option<usize> {
+        self.entries.iter().position(|id| id == pane_id)
+    }
+
+    /// Most recently focused pane other than `current` that still exists.
+    pub fn previous(&self, current: &str, exists: impl Fn(&str) -> bool) -> Option<&str> {
+        self.entries
+            .iter()
+            .map(String::as_str)
+            .find(|&id| id != current && exists(id))
+    }
+}
@@ -7,6 +7,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use crate::error::Result;
 use crate::tmux::TmuxClient;
 
+/// A window position as `(session, window_index)`
+pub type WindowAddress = (String, u32);
+
 pub struct WindowHistory {
     file_path: PathBuf,
     entries: HashMap<String, u64>,
@@ -79,8 +82,70 @@ impl WindowHistory {
         Ok(())
     }
 
+    /// Re-keys entries of windows that changed position, keeping their timestamps.
+    ///
+    /// All old keys are removed before the new ones are inserted, so windows that
+    /// swapped places keep their own history.
+    pub fn remap(&mut self, moves: &[(WindowAddress, WindowAddress)]) {
+        let timestamps: Vec<_> = moves
+            .iter()
+            .map(|((session, index), to)| {
+                let window_id = format!("{}:{}", session, index);
+                (self.entries.remove(&window_id), to)
+            })
+            .collect();
+
+        for (timestamp, (session, index)) in timestamps {
+            if let Some(timestamp) = timestamp {
+                self.entries
+                    .insert(format!("{}:{}", session, index), timestamp);
+            }
+        }
+    }
+
     pub fn get_last_access(&self, session: &str, window_index: u32) -> Option<u64> {
         let window_id = format!("{}:{}", session, window_index);
         self.entries.get(&window_id).cloned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(session: &str, index: u32) -> WindowAddress {
+        (session.to_string(), index)
+    }
+
+    #[test]
+    fn swapped_windows_keep_their_own_history() {
+        let mut history = WindowHistory::new(PathBuf::new());
+        history.entries.insert("work:1".to_string(), 100);
+        history.entries.insert("work:2".to_string(), 200);
+
+        history.remap(&[
+            (address("work", 1), address("work", 2)),
+            (address("work", 2), address("work", 1)),
+        ]);
+
+        assert_eq!(history.get_last_access("work", 1), Some(200));
+        assert_eq!(history.get_last_access("work", 2), Some(100));
+    }
+
+    #[test]
+    fn moved_windows_follow_to_their_new_session() {
+        let mut history = WindowHistory::new(PathBuf::new());
+        history.entries.insert("work:3".to_string(), 300);
+        history.entries.insert("play:1".to_string(), 50);
+
+        history.remap(&[
+            (address("work", 3), address("play", 2)),
+            (address("work", 4), address("play", 3)),
+        ]);
+
+        assert_eq!(history.get_last_access("work", 3), None);
+        assert_eq!(history.get_last_access("play", 2), Some(300));
+        assert_eq!(history.get_last_access("play", 1), Some(50));
+        assert_eq!(history.get_last_access("play", 3), None);
+    }
+}
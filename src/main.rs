@@ -1,16 +1,28 @@
 mod cli;
+mod config;
+mod directories;
 mod error;
 mod fzf;
+mod git;
 mod history;
+mod naming;
 mod paths;
+mod process;
 mod tmux;
+mod workspace;
 mod zoxide;
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use cli::Cli;
+use cli::completions::COMPLETE_VAR;
 use tmux::TmuxClient;
 
 fn main() -> error::Result<()> {
+    CompleteEnv::with_factory(Cli::command)
+        .var(COMPLETE_VAR)
+        .complete();
+
     let cli = Cli::parse();
     let client = TmuxClient::new();
 
@@ -0,0 +1,241 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::git::{Repository, parse_remote_url};
+
+/// How a session name is derived from its directory
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(try_from = "String")]
+pub enum NamingStrategy {
+    /// Directory name, e.g. `api`
+    #[default]
+    Basename,
+    /// Parent and directory name, e.g. `work/api`
+    Parent,
+    /// Name of the enclosing git repository
+    GitRepo,
+    /// `owner/repo` from the `origin` remote
+    GitRemote,
+    /// Repository and checked out branch, e.g. `api@main`
+    RepoBranch,
+    /// User template with `{basename}`, `{parent}`, `{repo}`, `{host}`, `{owner}`
+    /// and `{branch}` placeholders
+    Template(String),
+}
+
+impl FromStr for NamingStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "basename" => Ok(Self::Basename),
+            "parent" => Ok(Self::Parent),
+            "git-repo" => Ok(Self::GitRepo),
+            "git-remote" => Ok(Self::GitRemote),
+            "repo-branch" => Ok(Self::RepoBranch),
+            template if template.contains('{') => Ok(Self::Template(template.to_string())),
+            other => Err(format!(
+                "unknown naming strategy '{}', expected basename, parent, git-repo, git-remote, \
+                 repo-branch or a template like \"{{repo}}-{{branch}}\"",
+                other
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for NamingStrategy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Derive a session name for `path` using the given strategy.
+///
+/// Git-based strategies fall back to the directory name outside a repository.
+pub fn session_name(path: &Path, strategy: &NamingStrategy) -> String {
+    let basename = file_name(path);
+    let parent = path.parent().map(file_name).unwrap_or_default();
+    let repo = Repository::discover(path);
+    let repo_name = repo.as_ref().map(|r| r.name());
+
+    let name = match strategy {
+        NamingStrategy::Basename => basename,
+        NamingStrategy::Parent if parent.is_empty() => basename,
+        NamingStrategy::Parent => format!("{}/{}", parent, basename),
+        NamingStrategy::GitRepo => repo_name.unwrap_or(basename),
+        NamingStrategy::GitRemote => repo
+            .as_ref()
+            .and_then(|r| r.remote_url("origin"))
+            .and_then(|url| parse_remote_url(&url))
+            .map(|slug| format!("{}/{}", slug.owner, slug.repo))
+            .or(repo_name)
+            .unwrap_or(basename),
+        NamingStrategy::RepoBranch => match (&repo_name, repo.as_ref().and_then(|r| r.branch())) {
+            (Some(repo_name), Some(branch)) => format!("{}@{}", repo_name, branch),
+            _ => repo_name.unwrap_or(basename),
+        },
+        NamingStrategy::Template(template) => {
+            let slug = repo
+                .as_ref()
+                .and_then(|r| r.remote_url("origin"))
+                .and_then(|url| parse_remote_url(&url));
+            let (host, owner) = slug.map(|slug| (slug.host, slug.owner)).unwrap_or_default();
+            let branch = repo.as_ref().and_then(|r| r.branch()).unwrap_or_default();
+
+            let rendered = template
+                .replace("{basename}", &basename)
+                .replace("{parent}", &parent)
+                .replace("{repo}", repo_name.as_deref().unwrap_or(&basename))
+                .replace("{host}", &host)
+                .replace("{owner}", &owner)
+                .replace("{branch}", &branch);
+
+            // Drop separators left dangling by empty placeholders
+            let trimmed = rendered.trim_matches(|c| matches!(c, '-' | '_' | '@' | '/'));
+            if trimmed.is_empty() {
+                basename
+            } else {
+                trimmed.to_string()
+            }
+        }
+    };
+
+    sanitise_session_name(&name)
+}
+
+/// Derive a session name for `path` that is not in `taken`.
+///
+/// When the preferred name belongs to a session rooted elsewhere, falls back to
+/// the `parent/basename` form and then to numbered suffixes.
+pub fn unique_session_name(path: &Path, strategy: &NamingStrategy, taken: &[String]) -> String {
+    let preferred = session_name(path, strategy);
+    if !taken.contains(&preferred) {
+        return preferred;
+    }
+
+    let disambiguated = session_name(path, &NamingStrategy::Parent);
+    if !taken.contains(&disambiguated) {
+        return disambiguated;
+    }
+
+    (2..)
+        .map(|n| format!("{}-{}", disambiguated, n))
+        .find(|name| !taken.contains(name))
+        .unwrap_or(disambiguated)
+}
+
+/// Make a name safe to use as a tmux session name.
+///
+/// tmux does not allow `:` and `.` in session names, `#` starts a format, and a leading
+/// `=`, `$`, `@` or `%` changes how a target is resolved. Leading dots are dropped so
+/// hidden directories get readable names.
+pub fn sanitise_session_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .trim_start_matches(['.', '=', '$', '@', '%'])
+        .chars()
+        .map(|c| {
+            if c.is_whitespace() || c.is_control() || matches!(c, ':' | '.' | '#') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    if name.is_empty() {
+        "_".to_string()
+    } else {
+        name
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Outside any git repository, so git strategies fall back to the directory name
+    const PATH: &str = "/nonexistent/tsm.d/=my.app:v1#2";
+
+    #[test]
+    fn special_characters_are_replaced() {
+        assert_eq!(sanitise_session_name("api:v1"), "api_v1");
+        assert_eq!(sanitise_session_name("my.app"), "my_app");
+        assert_eq!(sanitise_session_name("issue#12"), "issue_12");
+        assert_eq!(
+            sanitise_session_name("two words\tand\ttabs"),
+            "two_words_and_tabs"
+        );
+    }
+
+    #[test]
+    fn target_prefixes_are_dropped() {
+        assert_eq!(sanitise_session_name("=main"), "main");
+        assert_eq!(sanitise_session_name("$1"), "1");
+        assert_eq!(sanitise_session_name("@2"), "2");
+        assert_eq!(sanitise_session_name("%3"), "3");
+        assert_eq!(sanitise_session_name(".config"), "config");
+    }
+
+    #[test]
+    fn empty_names_become_an_underscore() {
+        assert_eq!(sanitise_session_name(""), "_");
+        assert_eq!(sanitise_session_name(" ..= "), "_");
+    }
+
+    #[test]
+    fn every_strategy_is_sanitised() {
+        let path = Path::new(PATH);
+
+        for strategy in [
+            NamingStrategy::Basename,
+            NamingStrategy::GitRepo,
+            NamingStrategy::GitRemote,
+            NamingStrategy::RepoBranch,
+            NamingStrategy::Template("{basename}".to_string()),
+        ] {
+            assert_eq!(
+                session_name(path, &strategy),
+                "my_app_v1_2",
+                "{:?}",
+                strategy
+            );
+        }
+
+        assert_eq!(
+            session_name(path, &NamingStrategy::Parent),
+            "tsm_d/=my_app_v1_2"
+        );
+    }
+
+    #[test]
+    fn unique_names_fall_back_to_parent_then_suffixes() {
+        let path = Path::new("/work/api");
+        let taken = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let strategy = NamingStrategy::Basename;
+
+        assert_eq!(unique_session_name(path, &strategy, &taken(&[])), "api");
+        assert_eq!(
+            unique_session_name(path, &strategy, &taken(&["api"])),
+            "work/api"
+        );
+        assert_eq!(
+            unique_session_name(path, &strategy, &taken(&["api", "work/api"])),
+            "work/api-2"
+        );
+        assert_eq!(
+            unique_session_name(path, &strategy, &taken(&["api", "work/api", "work/api-2"])),
+            "work/api-3"
+        );
+    }
+}
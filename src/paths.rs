@@ -1,13 +1,53 @@
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Get the history file path with the following priority:
 /// 1. TSM_HISTORY_FILE environment variable
 /// 2. XDG_STATE_HOME/tsm/history (or ~/.local/state/tsm/history)
 pub fn history_file_path() -> PathBuf {
+    state_file_path("TSM_HISTORY_FILE", "history")
+}
+
+/// Get the jumplist file path with the following priority:
+/// 1. TSM_JUMPLIST_FILE environment variable
+/// 2. XDG_STATE_HOME/tsm/jumplist (or ~/.local/state/tsm/jumplist)
+pub fn jumplist_file_path() -> PathBuf {
+    state_file_path("TSM_JUMPLIST_FILE", "jumplist")
+}
+
+/// Get the pane history file path with the following priority:
+/// 1. TSM_PANE_HISTORY_FILE environment variable
+/// 2. XDG_STATE_HOME/tsm/pane_history (or ~/.local/state/tsm/pane_history)
+pub fn pane_history_file_path() -> PathBuf {
+    state_file_path("TSM_PANE_HISTORY_FILE", "pane_history")
+}
+
+/// Get the marks file path with the following priority:
+/// 1. TSM_MARKS_FILE environment variable
+/// 2. XDG_STATE_HOME/tsm/marks (or ~/.local/state/tsm/marks)
+pub fn marks_file_path() -> PathBuf {
+    state_file_path("TSM_MARKS_FILE", "marks")
+}
+
+/// Get the bookmarks file path with the following priority:
+/// 1. TSM_BOOKMARKS_FILE environment variable
+/// 2. XDG_STATE_HOME/tsm/bookmarks (or ~/.local/state/tsm/bookmarks)
+pub fn bookmarks_file_path() -> PathBuf {
+    state_file_path("TSM_BOOKMARKS_FILE", "bookmarks")
+}
+
+/// Get the path of the file tracking an uncommitted `tsm cycle` walk:
+/// 1. TSM_CYCLE_FILE environment variable
+/// 2. XDG_STATE_HOME/tsm/cycle (or ~/.local/state/tsm/cycle)
+pub fn cycle_file_path() -> PathBuf {
+    state_file_path("TSM_CYCLE_FILE", "cycle")
+}
+
+/// Resolve a file in tsm's state directory, creating its parent directory
+fn state_file_path(env_var: &str, name: &str) -> PathBuf {
     // Environment variable override
-    if let Ok(custom_path) = env::var("TSM_HISTORY_FILE") {
+    if let Ok(custom_path) = env::var(env_var) {
         let path = PathBuf::from(custom_path);
         if let Some(parent) = path.parent() {
             let _ = fs::create_dir_all(parent);
@@ -17,15 +57,15 @@ pub fn history_file_path() -> PathBuf {
 
     // XDG Base Directory (state)
     let xdg_path = if let Ok(xdg_state_home) = env::var("XDG_STATE_HOME") {
-        PathBuf::from(xdg_state_home).join("tsm").join("history")
+        PathBuf::from(xdg_state_home).join("tsm").join(name)
     } else if let Ok(home) = env::var("HOME") {
         PathBuf::from(home)
             .join(".local")
             .join("state")
             .join("tsm")
-            .join("history")
+            .join(name)
     } else {
-        PathBuf::from(".tsm_history")
+        PathBuf::from(format!(".tsm_{}", name))
     };
 
     // Ensure directory exists
@@ -35,3 +75,56 @@ pub fn history_file_path() -> PathBuf {
 
     xdg_path
 }
+
+/// Get the config file path with the following priority:
+/// 1. TSM_CONFIG_FILE environment variable
+/// 2. XDG_CONFIG_HOME/tsm/config.toml (or ~/.config/tsm/config.toml)
+pub fn config_file_path() -> PathBuf {
+    // Environment variable override
+    if let Ok(custom_path) = env::var("TSM_CONFIG_FILE") {
+        return PathBuf::from(custom_path);
+    }
+
+    // XDG Base Directory (config)
+    if let Ok(xdg_config_home) = env::var("XDG_CONFIG_HOME") {
+        PathBuf::from(xdg_config_home)
+            .join("tsm")
+            .join("config.toml")
+    } else if let Ok(home) = env::var("HOME") {
+        PathBuf::from(home)
+            .join(".config")
+            .join("tsm")
+            .join("config.toml")
+    } else {
+        PathBuf::from(".tsm.toml")
+    }
+}
+
+/// Expand a leading `~` to the home directory and `.` to the current directory
+pub fn expand_path(path: &str) -> String {
+    if path.starts_with('~') {
+        std::env::home_dir()
+            .map(|home| path.replacen('~', &home.to_string_lossy(), 1))
+            .unwrap_or_else(|| path.to_string())
+    } else if path == "." {
+        std::env::current_dir()
+            .map(|cwd| cwd.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.to_string())
+    } else {
+        path.to_string()
+    }
+}
+
+/// Replaces the home directory prefix of `path` with `~`
+pub fn contract_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+
+    if let Some(home) = std::env::home_dir()
+        && let Some(rest) = path.strip_prefix(home.to_string_lossy().as_ref())
+        && (rest.is_empty() || rest.starts_with('/'))
+    {
+        return format!("~{}", rest);
+    }
+
+    path.to_string()
+}
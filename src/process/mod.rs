@@ -0,0 +1,310 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::process::Command;
+
+use crate::error::{Result, TsmError};
+
+/// Parent and children of every running process, read from `/proc`.
+///
+/// Empty where `/proc` is not available.
+pub struct ProcessTable {
+    parents: HashMap<u32, u32>,
+    children: HashMap<u32, Vec<u32>>,
+}
+
+impl ProcessTable {
+    pub fn load() -> Self {
+        let parents: HashMap<u32, u32> = fs::read_dir("/proc")
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+                    .filter_map(|pid| Some((pid, parent_pid(pid)?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self::from_parents(parents)
+    }
+
+    /// Builds the table from the parent of each process.
+    fn from_parents(parents: HashMap<u32, u32>) -> Self {
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (&pid, &parent) in &parents {
+            children.entry(parent).or_default().push(pid);
+        }
+        for pids in children.values_mut() {
+            pids.sort_unstable();
+        }
+
+        Self { parents, children }
+    }
+
+    /// `pid` followed by its parent, grandparent and so on up to init.
+    pub fn ancestors(&self, pid: u32) -> Vec<u32> {
+        let mut chain = vec![pid];
+
+        while let Some(&parent) = self.parents.get(chain.last().unwrap_or(&0)) {
+            if parent == 0 || chain.contains(&parent) {
+                break;
+            }
+            chain.push(parent);
+        }
+
+        chain
+    }
+
+    /// `pid` followed by all of its descendants, parents before children.
+    ///
+    /// A pid reused while `/proc` was read can make the table cyclic, so every process
+    /// is visited once.
+    pub fn descendants(&self, pid: u32) -> Vec<u32> {
+        let mut tree = vec![pid];
+        let mut visited = HashSet::from([pid]);
+        let mut i = 0;
+
+        while i < tree.len() {
+            if let Some(children) = self.children.get(&tree[i]) {
+                tree.extend(children.iter().filter(|child| visited.insert(**child)));
+            }
+            i += 1;
+        }
+
+        tree
+    }
+}
+
+/// A TCP socket in the LISTEN state
+#[derive(Debug, PartialEq)]
+pub struct Listener {
+    pub port: u16,
+    pub inode: u64,
+}
+
+/// Listening TCP sockets, IPv4 and IPv6, read from `/proc/net/tcp` and `/proc/net/tcp6`.
+pub fn listening_sockets() -> Vec<Listener> {
+    ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|table| parse_tcp_table(&table))
+        .collect()
+}
+
+/// Listening sockets in the contents of a `/proc/net/tcp` or `/proc/net/tcp6` table.
+fn parse_tcp_table(table: &str) -> Vec<Listener> {
+    const LISTEN: &str = "0A";
+
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.get(3) != Some(&LISTEN) {
+                return None;
+            }
+
+            let (_, port) = fields.get(1)?.rsplit_once(':')?;
+            Some(Listener {
+                port: u16::from_str_radix(port, 16).ok()?,
+                inode: fields.get(9)?.parse().ok()?,
+            })
+        })
+        .filter(|listener| listener.inode != 0)
+        .collect()
+}
+
+/// Processes holding each of the socket `inodes` open, found through `/proc/<pid>/fd`.
+///
+/// Only processes whose file descriptors are readable by the current user are found.
+pub fn socket_owners(inodes: &[u64]) -> HashMap<u64, Vec<u32>> {
+    let mut owners: HashMap<u64, Vec<u32>> = HashMap::new();
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return owners;
+    };
+
+    for pid in entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+    {
+        let Ok(fds) = fs::read_dir(format!("/proc/{}/fd", pid)) else {
+            continue;
+        };
+
+        for fd in fds.filter_map(|fd| fd.ok()) {
+            let Ok(target) = fs::read_link(fd.path()) else {
+                continue;
+            };
+
+            if let Some(inode) = target
+                .to_str()
+                .and_then(|t| t.strip_prefix("socket:["))
+                .and_then(|t| t.strip_suffix(']'))
+                .and_then(|t| t.parse::<u64>().ok())
+                && inodes.contains(&inode)
+            {
+                let pids = owners.entry(inode).or_default();
+                if !pids.contains(&pid) {
+                    pids.push(pid);
+                }
+            }
+        }
+    }
+
+    owners
+}
+
+/// Sends SIGTERM to a process.
+pub fn terminate(pid: u32) -> Result<()> {
+    let output = Command::new("kill").arg(pid.to_string()).output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(TsmError::Kill(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+fn parent_pid(pid: u32) -> Option<u32> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    stat.rsplit_once(')')?
+        .1
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+/// Command line of the process in the foreground of the terminal `shell_pid` runs in.
+///
+/// Returns `None` when the shell itself is in the foreground, i.e. the pane is idle,
+/// or when `/proc` is not available.
+pub fn foreground_command(shell_pid: u32) -> Option<String> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", shell_pid)).ok()?;
+
+    // The command name may contain spaces and parentheses, so split after the last ')'
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let tpgid: i64 = fields.get(5)?.parse().ok()?;
+
+    if tpgid <= 0 || tpgid == i64::from(shell_pid) {
+        return None;
+    }
+
+    command_line(tpgid as u32)
+}
+
+/// Command line of a process, quoted for sh.
+pub fn command_line(pid: u32) -> Option<String> {
+    let args = cmdline(pid)?;
+    Some(
+        args.iter()
+            .map(|arg| shell_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// Arguments of a running process.
+pub fn cmdline(pid: u32) -> Option<Vec<String>> {
+    let raw = fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+
+    let args: Vec<String> = raw
+        .split(|b| *b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).to_string())
+        .collect();
+
+    (!args.is_empty()).then_some(args)
+}
+
+/// Quotes `arg` for sh unless it only contains characters that need no quoting.
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,@%+~".contains(c));
+
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1 ─ 10 ─┬─ 20 ─ 40
+    ///         └─ 30
+    fn table() -> ProcessTable {
+        ProcessTable::from_parents(HashMap::from([
+            (1, 0),
+            (10, 1),
+            (30, 10),
+            (20, 10),
+            (40, 20),
+        ]))
+    }
+
+    #[test]
+    fn ancestors_up_to_init() {
+        assert_eq!(table().ancestors(40), vec![40, 20, 10, 1]);
+        assert_eq!(table().ancestors(1), vec![1]);
+        assert_eq!(table().ancestors(99), vec![99]);
+    }
+
+    #[test]
+    fn descendants_parents_first() {
+        assert_eq!(table().descendants(10), vec![10, 20, 30, 40]);
+        assert_eq!(table().descendants(30), vec![30]);
+    }
+
+    #[test]
+    fn cycles_from_reused_pids_terminate() {
+        let cyclic = ProcessTable::from_parents(HashMap::from([(5, 7), (7, 6), (6, 5)]));
+        assert_eq!(cyclic.ancestors(5), vec![5, 7, 6]);
+        assert_eq!(cyclic.descendants(5), vec![5, 6, 7]);
+    }
+
+    const TCP: &str = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:07E8 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 662 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1538 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 0 1 0000000000000000 100 0 0 10 0
+   2: 0100007F:07E8 0100007F:C350 01 00000000:00000000 00:00000000 00000000  1000        0 771 1 0000000000000000 20 4 30 10 -1
+";
+
+    const TCP6: &str = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:1F9C 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 12345 1 0000000000000000 100 0 0 10 0
+";
+
+    #[test]
+    fn ipv4_listeners() {
+        assert_eq!(
+            parse_tcp_table(TCP),
+            vec![Listener {
+                port: 2024,
+                inode: 662
+            }]
+        );
+    }
+
+    #[test]
+    fn ipv6_listeners() {
+        assert_eq!(
+            parse_tcp_table(TCP6),
+            vec![Listener {
+                port: 8092,
+                inode: 12345
+            }]
+        );
+    }
+
+    #[test]
+    fn header_only_table() {
+        assert!(parse_tcp_table(TCP.lines().next().unwrap()).is_empty());
+    }
+}
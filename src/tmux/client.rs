@@ -1,7 +1,14 @@
-use super::Window;
+use super::{Pane, Window};
 use crate::error::{Result, TsmError};
 use std::process::Command;
 
+/// Format of the pane lines parsed by `parse_pane`.
+///
+/// `#{pane_title}` is set by the running program and may contain tabs, so it comes last.
+const PANE_FORMAT: &str = "#{pane_id}\t#{session_name}\t#{window_index}\t#{window_id}\t\
+                           #{pane_index}\t#{pane_pid}\t#{pane_height}\t#{pane_current_command}\t\
+                           #{pane_current_path}\t#{pane_title}";
+
 pub struct TmuxClient;
 
 impl TmuxClient {
@@ -41,12 +48,63 @@ impl TmuxClient {
             .collect()
     }
 
+    /// Whether a session named exactly `name` exists, attached or not.
+    pub fn has_session(&self, name: &str) -> bool {
+        self.tmux_cmd()
+            .arg("has-session")
+            .arg("-t")
+            .arg(format!("={}", name))
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Identifies the running server by its pid and start time, or empty if none is running.
+    ///
+    /// tmux numbers windows and panes from zero again after a restart, so IDs saved to
+    /// disk are only meaningful together with the server they came from.
+    pub fn server_id(&self) -> String {
+        self.tmux_cmd()
+            .arg("display-message")
+            .arg("-p")
+            .arg("#{pid}-#{start_time}")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Lists sessions with the directory each one is rooted in.
+    pub fn list_session_paths(&self) -> Vec<(String, String)> {
+        self.tmux_cmd()
+            .arg("list-sessions")
+            .arg("-F")
+            .arg("#{session_name}\t#{session_path}")
+            .output()
+            .map(|output| {
+                if output.status.success() {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    stdout
+                        .lines()
+                        .filter_map(|line| {
+                            let (name, path) = line.split_once('\t')?;
+                            Some((name.to_string(), path.to_string()))
+                        })
+                        .collect()
+                } else {
+                    vec![]
+                }
+            })
+            .unwrap_or_else(|_| vec![])
+    }
+
     pub fn list_windows(&self) -> Vec<Window> {
         self.tmux_cmd()
             .arg("list-windows")
             .arg("-a")
             .arg("-F")
-            .arg("#{session_name}\t#{window_index}\t#{window_name}\t#{pane_id}")
+            .arg("#{session_name}\t#{window_index}\t#{window_name}\t#{pane_id}\t#{window_id}")
             .output()
             .map(|output| {
                 if output.status.success() {
@@ -55,8 +113,9 @@ impl TmuxClient {
                         .lines()
                         .filter_map(|line| {
                             let parts: Vec<&str> = line.split('\t').collect();
-                            if parts.len() >= 4 {
+                            if parts.len() >= 5 {
                                 Some(Window {
+                                    id: parts[4].to_string(),
                                     session_name: parts[0].to_string(),
                                     index: parts[1].parse().ok()?,
                                     name: parts[2].to_string(),
@@ -74,6 +133,44 @@ impl TmuxClient {
             .unwrap_or_else(|_| vec![])
     }
 
+    /// Lists the panes of every session.
+    pub fn list_panes(&self) -> Vec<Pane> {
+        self.tmux_cmd()
+            .arg("list-panes")
+            .arg("-a")
+            .arg("-F")
+            .arg(PANE_FORMAT)
+            .output()
+            .map(|output| {
+                if output.status.success() {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    stdout.lines().filter_map(parse_pane).collect()
+                } else {
+                    vec![]
+                }
+            })
+            .unwrap_or_else(|_| vec![])
+    }
+
+    /// The pane the command was run from.
+    pub fn current_pane(&self) -> Result<Pane> {
+        let output = self
+            .tmux_cmd()
+            .arg("display-message")
+            .arg("-p")
+            .arg(PANE_FORMAT)
+            .output()?;
+
+        if output.status.success() {
+            parse_pane(String::from_utf8_lossy(&output.stdout).trim_end())
+                .ok_or_else(|| TsmError::TmuxCommand("Failed to parse current pane".to_string()))
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
     pub fn new_session(&self, name: String, path: String) -> Result<()> {
         let output = self
             .tmux_cmd()
@@ -98,6 +195,30 @@ impl TmuxClient {
         }
     }
 
+    /// Creates a detached session and returns the ID of its initial window.
+    pub fn new_detached_session(&self, name: &str, path: &str) -> Result<String> {
+        let output = self
+            .tmux_cmd()
+            .arg("new-session")
+            .arg("-d")
+            .arg("-P")
+            .arg("-F")
+            .arg("#{window_id}")
+            .arg("-s")
+            .arg(name)
+            .arg("-c")
+            .arg(path)
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
     pub fn kill_session(&self, session: &str) -> Result<()> {
         if self.is_inside_tmux() {
             let current = self.current_session().ok();
@@ -127,6 +248,23 @@ impl TmuxClient {
         }
     }
 
+    pub fn kill_window(&self, target: &str) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("kill-window")
+            .arg("-t")
+            .arg(target)
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
     pub fn kill_all_sessions(&self) -> Result<()> {
         self.tmux_cmd().arg("kill-server").output()?;
         Ok(())
@@ -258,6 +396,102 @@ impl TmuxClient {
         }
     }
 
+    /// ID (`@N`) of the current window, which is stable across moves and renumbering.
+    pub fn current_window_id(&self) -> Result<String> {
+        let output = self
+            .tmux_cmd()
+            .arg("display-message")
+            .arg("-p")
+            .arg("#{window_id}")
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Creates a window at the end of `session` without switching to it, returning its ID.
+    pub fn new_window(&self, session: &str, name: &str, path: &str) -> Result<String> {
+        let output = self
+            .tmux_cmd()
+            .arg("new-window")
+            .arg("-d")
+            .arg("-P")
+            .arg("-F")
+            .arg("#{window_id}")
+            .arg("-t")
+            .arg(format!("{}:", session))
+            .arg("-n")
+            .arg(name)
+            .arg("-c")
+            .arg(path)
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    pub fn rename_window(&self, target: &str, name: &str) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("rename-window")
+            .arg("-t")
+            .arg(target)
+            .arg(name)
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Types `command` into a pane and presses Enter.
+    pub fn send_command(&self, target: &str, command: &str) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("send-keys")
+            .arg("-t")
+            .arg(target)
+            .arg("-l")
+            .arg(command)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let output = self
+            .tmux_cmd()
+            .arg("send-keys")
+            .arg("-t")
+            .arg(target)
+            .arg("Enter")
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
     pub fn move_window(&self, from_session: &str, from_index: u32, to_session: &str) -> Result<()> {
         let output = self
             .tmux_cmd()
@@ -277,6 +511,46 @@ impl TmuxClient {
         }
     }
 
+    /// Moves a window into the position of another window, killing the latter.
+    pub fn replace_window(&self, from_session: &str, from_index: u32, target: &str) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("move-window")
+            .arg("-k")
+            .arg("-s")
+            .arg(format!("{}:{}", from_session, from_index))
+            .arg("-t")
+            .arg(target)
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    pub fn get_pane_current_path(&self, session: &str, window_index: u32) -> Result<String> {
+        let output = self
+            .tmux_cmd()
+            .arg("display-message")
+            .arg("-p")
+            .arg("-t")
+            .arg(format!("{}:{}", session, window_index))
+            .arg("#{pane_current_path}")
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
     pub fn get_pane_id(&self, session: &str, window_index: u32) -> Result<String> {
         let output = self
             .tmux_cmd()
@@ -338,6 +612,233 @@ impl TmuxClient {
         }
     }
 
+    pub fn swap_windows_in_session(
+        &self,
+        session: &str,
+        source_index: u32,
+        target_index: u32,
+    ) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("swap-window")
+            .arg("-d")
+            .arg("-s")
+            .arg(format!("{}:{}", session, source_index))
+            .arg("-t")
+            .arg(format!("{}:{}", session, target_index))
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    pub fn renumber_windows(&self, session: &str) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("move-window")
+            .arg("-r")
+            .arg("-t")
+            .arg(format!("{}:", session))
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    pub fn active_window_id(&self, session: &str) -> Result<String> {
+        let output = self
+            .tmux_cmd()
+            .arg("display-message")
+            .arg("-p")
+            .arg("-t")
+            .arg(format!("{}:", session))
+            .arg("#{window_id}")
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    pub fn select_window(&self, target: &str) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("select-window")
+            .arg("-t")
+            .arg(target)
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    pub fn select_pane(&self, target: &str) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("select-pane")
+            .arg("-t")
+            .arg(target)
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Captures the visible contents of a pane, keeping colours.
+    pub fn capture_pane(&self, target: &str) -> Result<String> {
+        let output = self
+            .tmux_cmd()
+            .arg("capture-pane")
+            .arg("-e")
+            .arg("-p")
+            .arg("-t")
+            .arg(target)
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Captures the plain text of a pane including up to `lines` lines of scrollback.
+    ///
+    /// The output always ends with the full visible area of the pane.
+    pub fn capture_scrollback(&self, target: &str, lines: u32) -> Result<String> {
+        let output = self
+            .tmux_cmd()
+            .arg("capture-pane")
+            .arg("-p")
+            .arg("-S")
+            .arg(format!("-{}", lines))
+            .arg("-t")
+            .arg(target)
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Captures lines `start..=end` of a pane, keeping colours.
+    ///
+    /// Line 0 is the top of the visible area; negative lines are in the scrollback.
+    pub fn capture_pane_lines(&self, target: &str, start: i64, end: i64) -> Result<String> {
+        let output = self
+            .tmux_cmd()
+            .arg("capture-pane")
+            .arg("-e")
+            .arg("-p")
+            .arg("-S")
+            .arg(start.to_string())
+            .arg("-E")
+            .arg(end.to_string())
+            .arg("-t")
+            .arg(target)
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Number of scrollback lines of a pane
+    pub fn history_size(&self, target: &str) -> Result<u32> {
+        let output = self
+            .tmux_cmd()
+            .arg("display-message")
+            .arg("-p")
+            .arg("-t")
+            .arg(target)
+            .arg("#{history_size}")
+            .output()?;
+
+        if output.status.success() {
+            String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .parse()
+                .map_err(|_| TsmError::TmuxCommand("Failed to parse history size".to_string()))
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    pub fn copy_mode(&self, target: &str) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("copy-mode")
+            .arg("-t")
+            .arg(target)
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Sends a copy-mode command and its arguments (`send-keys -X`), repeated `count` times.
+    pub fn send_copy_command(&self, target: &str, command: &[&str], count: u32) -> Result<()> {
+        let output = self
+            .tmux_cmd()
+            .arg("send-keys")
+            .arg("-X")
+            .arg("-N")
+            .arg(count.to_string())
+            .arg("-t")
+            .arg(target)
+            .args(command)
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsmError::TmuxCommand(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
     pub fn is_last_window_in_session(&self, session: &str) -> bool {
         let windows = self.list_windows();
         let count = windows.iter().filter(|w| w.session_name == session).count();
@@ -396,7 +897,27 @@ impl TmuxClient {
             })
             .unwrap_or_else(|_| vec![]);
 
-        sessions.sort_by(|a, b| b.1.cmp(&a.1));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.1));
         sessions
     }
 }
+
+fn parse_pane(line: &str) -> Option<Pane> {
+    let parts: Vec<&str> = line.splitn(10, '\t').collect();
+    if parts.len() < 10 {
+        return None;
+    }
+
+    Some(Pane {
+        id: parts[0].to_string(),
+        session_name: parts[1].to_string(),
+        window_index: parts[2].parse().ok()?,
+        window_id: parts[3].to_string(),
+        index: parts[4].parse().ok()?,
+        pid: parts[5].parse().ok()?,
+        height: parts[6].parse().ok()?,
+        command: parts[7].to_string(),
+        path: parts[8].to_string(),
+        title: parts[9].to_string(),
+    })
+}
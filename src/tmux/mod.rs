@@ -1,5 +1,7 @@
 pub mod client;
+pub mod pane;
 pub mod window;
 
 pub use client::TmuxClient;
+pub use pane::Pane;
 pub use window::Window;
@@ -0,0 +1,13 @@
+#[derive(Clone)]
+pub struct Pane {
+    pub id: String,
+    pub session_name: String,
+    pub window_index: u32,
+    pub window_id: String,
+    pub index: u32,
+    pub command: String,
+    pub title: String,
+    pub path: String,
+    pub pid: u32,
+    pub height: u32,
+}
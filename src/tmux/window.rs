@@ -1,4 +1,6 @@
+#[derive(Clone)]
 pub struct Window {
+    pub id: String,
     pub session_name: String,
     pub index: u32,
     pub name: String,
@@ -0,0 +1,366 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+
+/// Tool that declares a workspace
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WorkspaceKind {
+    /// `[workspace] members` in `Cargo.toml`
+    Cargo,
+    /// `workspaces` in `package.json` (npm, yarn, bun)
+    Npm,
+    /// `packages` in `pnpm-workspace.yaml`
+    Pnpm,
+}
+
+impl WorkspaceKind {
+    const ALL: [WorkspaceKind; 3] = [Self::Cargo, Self::Npm, Self::Pnpm];
+
+    /// File declaring the workspace members
+    fn manifest(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo.toml",
+            Self::Npm => "package.json",
+            Self::Pnpm => "pnpm-workspace.yaml",
+        }
+    }
+
+    /// File every member directory contains
+    fn member_manifest(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo.toml",
+            Self::Npm | Self::Pnpm => "package.json",
+        }
+    }
+
+    /// Reads the member globs declared in `contents`.
+    fn member_patterns(self, contents: &str) -> Vec<String> {
+        match self {
+            Self::Cargo => cargo_members(contents),
+            Self::Npm => npm_workspaces(contents),
+            Self::Pnpm => pnpm_packages(contents),
+        }
+    }
+}
+
+impl fmt::Display for WorkspaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cargo => write!(f, "cargo"),
+            Self::Npm => write!(f, "npm"),
+            Self::Pnpm => write!(f, "pnpm"),
+        }
+    }
+}
+
+/// A member package of a workspace
+#[derive(Clone, Debug)]
+pub struct Package {
+    pub path: PathBuf,
+    /// Name of the directory holding the workspace
+    pub workspace: String,
+    pub kind: WorkspaceKind,
+}
+
+/// Finds the packages of the workspaces at `roots`.
+///
+/// A root that is not a workspace itself has its immediate subdirectories checked
+/// instead, so a directory of checkouts can be configured as a single root.
+pub fn discover_packages(roots: &[PathBuf]) -> Vec<Package> {
+    let mut packages = Vec::new();
+
+    for root in roots {
+        let found = workspace_packages(root);
+        if !found.is_empty() {
+            packages.extend(found);
+            continue;
+        }
+
+        let Ok(entries) = fs::read_dir(root) else {
+            continue;
+        };
+
+        let mut children: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        children.sort();
+
+        for child in children {
+            packages.extend(workspace_packages(&child));
+        }
+    }
+
+    packages
+}
+
+/// Lists the members of every workspace declared directly in `dir`.
+fn workspace_packages(dir: &Path) -> Vec<Package> {
+    let workspace = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut packages: Vec<Package> = Vec::new();
+
+    for kind in WorkspaceKind::ALL {
+        let Ok(contents) = fs::read_to_string(dir.join(kind.manifest())) else {
+            continue;
+        };
+
+        for path in expand_members(dir, &kind.member_patterns(&contents), kind) {
+            // pnpm workspaces usually mirror `workspaces` in package.json
+            if packages.iter().any(|p| p.path == path) {
+                continue;
+            }
+
+            packages.push(Package {
+                path,
+                workspace: workspace.clone(),
+                kind,
+            });
+        }
+    }
+
+    packages
+}
+
+/// Resolves member globs relative to `dir`, honouring `!` exclusions.
+fn expand_members(dir: &Path, patterns: &[String], kind: WorkspaceKind) -> Vec<PathBuf> {
+    let excluded: Vec<Pattern> = patterns
+        .iter()
+        .filter_map(|p| p.strip_prefix('!'))
+        .filter_map(|p| Pattern::new(p.trim_start_matches("./")).ok())
+        .collect();
+
+    let mut members = Vec::new();
+
+    for pattern in patterns.iter().filter(|p| !p.starts_with('!')) {
+        let full = dir.join(pattern.trim_start_matches("./"));
+        let Ok(paths) = glob::glob(&full.to_string_lossy()) else {
+            continue;
+        };
+
+        for path in paths.flatten() {
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+
+            let skip = relative
+                .components()
+                .any(|c| c.as_os_str() == "node_modules")
+                || excluded.iter().any(|p| p.matches_path(relative))
+                || !path.join(kind.member_manifest()).is_file();
+
+            if !skip && path != dir && !members.contains(&path) {
+                members.push(path);
+            }
+        }
+    }
+
+    members.sort();
+    members
+}
+
+fn cargo_members(contents: &str) -> Vec<String> {
+    let Ok(manifest) = contents.parse::<toml::Table>() else {
+        return vec![];
+    };
+
+    let Some(workspace) = manifest.get("workspace").and_then(|w| w.as_table()) else {
+        return vec![];
+    };
+
+    let strings = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut patterns = strings("members");
+    patterns.extend(strings("exclude").into_iter().map(|p| format!("!{}", p)));
+    patterns
+}
+
+fn npm_workspaces(contents: &str) -> Vec<String> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(contents) else {
+        return vec![];
+    };
+
+    // Either an array of globs or yarn's `{ "packages": [...] }`
+    let workspaces = match manifest.get("workspaces") {
+        Some(serde_json::Value::Object(object)) => object.get("packages"),
+        other => other,
+    };
+
+    workspaces
+        .and_then(|w| w.as_array())
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads the `packages` list of `pnpm-workspace.yaml`.
+///
+/// Only the block and flow sequence forms used in practice are understood.
+fn pnpm_packages(contents: &str) -> Vec<String> {
+    let unquote = |value: &str| {
+        let value = value.trim();
+        match value.chars().next() {
+            // A quoted value ends at its closing quote; `#` inside it is not a comment
+            Some(quote @ ('\'' | '"')) => value[1..]
+                .split(quote)
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            _ => value
+                .split(" #")
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        }
+    };
+
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+
+    for line in contents.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = false;
+
+            if let Some(rest) = line.strip_prefix("packages:") {
+                let rest = rest.trim();
+                if let Some(flow) = rest.strip_prefix('[') {
+                    patterns.extend(
+                        flow.trim_end_matches(']')
+                            .split(',')
+                            .map(unquote)
+                            .filter(|p| !p.is_empty()),
+                    );
+                } else {
+                    in_packages = true;
+                }
+            }
+            continue;
+        }
+
+        if in_packages && let Some(item) = line.trim_start().strip_prefix('-') {
+            let item = unquote(item);
+            if !item.is_empty() {
+                patterns.push(item);
+            }
+        }
+    }
+
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pnpm_block_sequence_with_quotes_and_comments() {
+        let yaml = "\
+# workspace layout
+packages:
+  - 'apps/*'
+  - \"packages/*\" # shared code
+  # - 'old/*'
+
+  - '!**/test/**'
+catalog:
+  - not-a-package
+";
+
+        assert_eq!(
+            pnpm_packages(yaml),
+            vec!["apps/*", "packages/*", "!**/test/**"]
+        );
+    }
+
+    #[test]
+    fn pnpm_hash_inside_quotes_is_not_a_comment() {
+        let yaml = "packages:\n  - 'tools/#internal' # comment\n  - \"a #b\"\n";
+
+        assert_eq!(pnpm_packages(yaml), vec!["tools/#internal", "a #b"]);
+    }
+
+    #[test]
+    fn pnpm_flow_sequence() {
+        let yaml = "packages: ['apps/*', \"libs/*\" ]\n";
+
+        assert_eq!(pnpm_packages(yaml), vec!["apps/*", "libs/*"]);
+    }
+
+    #[test]
+    fn pnpm_unindented_block_sequence() {
+        let yaml = "packages:\n- apps/*\n- tools\nonlyBuiltDependencies:\n  - esbuild\n";
+
+        assert_eq!(pnpm_packages(yaml), vec!["apps/*", "tools"]);
+    }
+
+    #[test]
+    fn pnpm_without_packages() {
+        assert!(pnpm_packages("catalog:\n  react: ^18\n").is_empty());
+    }
+
+    #[test]
+    fn cargo_members_and_exclusions() {
+        let toml = r#"
+[workspace]
+members = ["crates/*", "cli"]
+exclude = ["crates/experimental"]
+"#;
+
+        assert_eq!(
+            cargo_members(toml),
+            vec!["crates/*", "cli", "!crates/experimental"]
+        );
+    }
+
+    #[test]
+    fn cargo_package_without_workspace() {
+        assert!(cargo_members("[package]\nname = \"tsm\"\n").is_empty());
+        assert!(cargo_members("not toml [").is_empty());
+    }
+
+    #[test]
+    fn npm_workspace_array() {
+        let json = r#"{ "name": "root", "workspaces": ["packages/*", "!packages/legacy"] }"#;
+
+        assert_eq!(npm_workspaces(json), vec!["packages/*", "!packages/legacy"]);
+    }
+
+    #[test]
+    fn yarn_workspace_object() {
+        let json = r#"{ "workspaces": { "packages": ["apps/*"], "nohoist": ["**/rn"] } }"#;
+
+        assert_eq!(npm_workspaces(json), vec!["apps/*"]);
+    }
+
+    #[test]
+    fn npm_without_workspaces() {
+        assert!(npm_workspaces(r#"{ "name": "app" }"#).is_empty());
+        assert!(npm_workspaces("{").is_empty());
+    }
+}
@@ -1,25 +1,52 @@
 use crate::error::TsmError;
+use std::path::PathBuf;
 use std::process::Command;
 
-pub fn query_directories() -> Result<Vec<String>, TsmError> {
-    match Command::new("zoxide").arg("query").arg("-l").output() {
-        Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let dirs = stdout
-                .lines()
-                .map(|line| {
-                    if let Some(home_dir) = std::env::home_dir() {
-                        let home_str = home_dir.to_string_lossy();
+/// A directory in the zoxide database
+pub struct RankedDirectory {
+    pub path: PathBuf,
+    pub score: f64,
+}
+
+/// Lists the directories matching `keywords`, best match first.
+///
+/// With no keywords the whole database is listed. No match yields an empty list.
+pub fn query_directories(keywords: &[String]) -> Result<Vec<RankedDirectory>, TsmError> {
+    let output = Command::new("zoxide")
+        .args(["query", "--list", "--score", "--"])
+        .args(keywords)
+        .output()
+        .map_err(|_| TsmError::ZoxideQueryFailed)?;
+
+    // zoxide exits with 1 when nothing matches
+    if !output.status.success() {
+        return Ok(vec![]);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let dirs = stdout
+        .lines()
+        .filter_map(|line| {
+            let (score, path) = line.trim_start().split_once(' ')?;
+            Some(RankedDirectory {
+                path: PathBuf::from(path),
+                score: score.parse().unwrap_or(0.0),
+            })
+        })
+        .collect();
+
+    Ok(dirs)
+}
 
-                        if line.starts_with(home_str.as_ref()) {
-                            return line.replacen(home_str.as_ref(), "~", 1);
-                        }
-                    }
-                    line.to_string()
-                })
-                .collect();
-            Ok(dirs)
-        }
-        Err(_) => Err(TsmError::ZoxideQueryFailed),
+/// Adds a directory to the zoxide database, or bumps its rank if already present.
+pub fn add_directory(path: &str) -> Result<(), TsmError> {
+    match Command::new("zoxide")
+        .arg("add")
+        .arg("--")
+        .arg(path)
+        .status()
+    {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err(TsmError::ZoxideQueryFailed),
     }
 }